@@ -0,0 +1,111 @@
+use crate::widgets::{label_click_to_copy, unit_value_click_to_copy};
+use eframe::egui;
+use eframe::egui::{Color32, Ui};
+use worldsmith_lib::atmosphere::{Atmosphere, Gas};
+use worldsmith_lib::planet::TerrestrialPlanet;
+use worldsmith_lib::unit_system::UnitSystem;
+use worldsmith_lib::units::{Bar, Kelvin, Unit};
+
+pub struct AtmosphereCalculator {
+    /// One for every gas in [Gas::ALL].
+    input_partial_pressures: Vec<(Gas, f32)>,
+    atmosphere: Atmosphere,
+}
+
+impl AtmosphereCalculator {
+    pub fn new(planet: &TerrestrialPlanet, surface_temperature: Kelvin) -> Self {
+        // Starts out as the atmosphere of the earth.
+        let earth = Atmosphere::earth_like_partial_pressures();
+        let input_partial_pressures = Gas::ALL
+            .iter()
+            .map(|&gas| {
+                let pressure = earth
+                    .iter()
+                    .find(|(g, _)| *g == gas)
+                    .map_or(0., |(_, pressure)| f32::from(*pressure));
+                (gas, pressure)
+            })
+            .collect();
+
+        AtmosphereCalculator {
+            input_partial_pressures,
+            atmosphere: Atmosphere::calculate_parameters(
+                earth,
+                planet.surface_gravity,
+                surface_temperature,
+            ),
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        planet: &TerrestrialPlanet,
+        surface_temperature: Kelvin,
+        unit_system: UnitSystem,
+    ) {
+        ui.heading("Atmosphere composition");
+
+        egui::Grid::new("atmosphere_composition")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                for (gas, pressure) in self.input_partial_pressures.iter_mut() {
+                    ui.label(format!("{} ({})", gas, gas.formula()));
+                    ui.add(egui::Slider::new(pressure, 0.0..=100.0).logarithmic(true));
+                    ui.label(Bar::SYMBOL).on_hover_text("Partial pressure");
+                    ui.end_row();
+                }
+
+                self.atmosphere = Atmosphere::calculate_parameters(
+                    self.input_partial_pressures
+                        .iter()
+                        .map(|(gas, pressure)| (*gas, (*pressure).into()))
+                        .collect(),
+                    planet.surface_gravity,
+                    surface_temperature,
+                );
+                let atmosphere = &self.atmosphere;
+
+                ui.label("Surface pressure");
+                unit_value_click_to_copy(ui, atmosphere.total_pressure, unit_system, 4);
+                ui.end_row();
+
+                ui.label("Mean molecular weight");
+                label_click_to_copy(ui, format!("{:.2}", atmosphere.mean_molecular_weight));
+                ui.label("g/mol").on_hover_text("gram per mole");
+                ui.end_row();
+
+                ui.label("Scale height")
+                    .on_hover_text("Height over which the pressure drops by a factor of e (2.7)");
+                unit_value_click_to_copy(ui, atmosphere.scale_height, unit_system, 2);
+                ui.end_row();
+
+                ui.label("Boiling point of water");
+                match atmosphere.water_boiling_point {
+                    Some(boiling_point) => {
+                        unit_value_click_to_copy(ui, boiling_point, unit_system, 0);
+                    }
+                    None => {
+                        ui.label("-").on_hover_text(
+                            "The pressure is too low for liquid water, ice turns directly into vapor",
+                        );
+                    }
+                }
+                ui.end_row();
+
+                ui.label("Breathable");
+                if atmosphere.is_breathable() {
+                    ui.colored_label(Color32::from_rgb(0, 180, 0), "yes");
+                } else {
+                    let hazards: Vec<String> =
+                        atmosphere.hazards.iter().map(|h| h.to_string()).collect();
+                    ui.colored_label(
+                        Color32::from_rgb(230, 80, 0),
+                        format!("no, {}", hazards.join(", ")),
+                    );
+                }
+                ui.end_row();
+            });
+    }
+}
@@ -0,0 +1,192 @@
+use crate::widgets::label_click_to_copy;
+use eframe::egui;
+use eframe::egui::{Color32, Ui};
+use worldsmith_lib::calendar::Calendar;
+use worldsmith_lib::orbit::Orbit;
+use worldsmith_lib::units::{EarthDay, Hour, Unit};
+
+pub struct CalendarCalculator {
+    input_rotation_period: f32,
+    input_year: f32,
+    input_has_moon: bool,
+    input_synodic_month: f32,
+    input_drift_years: u32,
+    /// Shown until the next export.
+    export_error: Option<String>,
+}
+
+impl CalendarCalculator {
+    pub fn new() -> Self {
+        // Starts out as the earth and our moon.
+        CalendarCalculator {
+            input_rotation_period: 23.9345,
+            input_year: 365.256,
+            input_has_moon: true,
+            input_synodic_month: 29.530588,
+            input_drift_years: 1000,
+            export_error: None,
+        }
+    }
+
+    /// The planet orbit is used when the user wants to copy the year of the planet calculator.
+    pub fn show(&mut self, ui: &mut Ui, planet_orbit: &Orbit) {
+        ui.heading("Calendar calculator");
+
+        egui::Grid::new("calendar_inputs")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Rotation period")
+                    .on_hover_text("Time for one rotation of the planet, relative to the stars");
+                ui.add(
+                    egui::Slider::new(&mut self.input_rotation_period, 1.0..=10_000.0)
+                        .logarithmic(true),
+                );
+                ui.label(Hour::SYMBOL).on_hover_text(Hour::NAME);
+                ui.end_row();
+
+                ui.label("Year")
+                    .on_hover_text("Orbital period of the planet");
+                ui.add(egui::Slider::new(&mut self.input_year, 1.0..=100_000.0).logarithmic(true));
+                ui.label(EarthDay::SYMBOL).on_hover_text(EarthDay::NAME);
+                ui.end_row();
+
+                ui.checkbox(&mut self.input_has_moon, "Moon");
+                ui.add_enabled(
+                    self.input_has_moon,
+                    egui::Slider::new(&mut self.input_synodic_month, 0.1..=10_000.0)
+                        .logarithmic(true),
+                )
+                .on_hover_text("Synodic month: time from one full moon to the next");
+                ui.label(EarthDay::SYMBOL).on_hover_text(EarthDay::NAME);
+                ui.end_row();
+            });
+
+        if ui.button("Use the year of the planet calculator").clicked() {
+            self.input_year = planet_orbit.period_days.into();
+        }
+
+        let calendar = Calendar::calculate_parameters(
+            self.input_rotation_period.into(),
+            self.input_year.into(),
+            self.input_has_moon.then(|| self.input_synodic_month.into()),
+        );
+
+        match calendar {
+            Some(calendar) => {
+                self.show_calendar(ui, &calendar);
+
+                if ui
+                    .button("Export months...")
+                    .on_hover_text("Save the months as a csv file, to open in a spreadsheet")
+                    .clicked()
+                {
+                    self.export_error = export_csv(&calendar).err().map(|e| e.to_string());
+                }
+                if let Some(error) = &self.export_error {
+                    ui.colored_label(Color32::from_rgb(230, 150, 0), format!("⚠ {}", error));
+                }
+            }
+            None => {
+                ui.colored_label(
+                    Color32::from_rgb(230, 150, 0),
+                    "⚠ The planet rotates as slowly as it orbits the star, so the sun doesn't move across the sky. There are no days to base a calendar on.",
+                );
+            }
+        }
+    }
+
+    fn show_calendar(&mut self, ui: &mut Ui, calendar: &Calendar) {
+        ui.heading("Calendar");
+
+        egui::Grid::new("calendar_parameters")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Day")
+                    .on_hover_text("Time from one noon to the next");
+                label_click_to_copy(ui, format!("{:.4}", calendar.solar_day));
+                ui.label(Hour::SYMBOL).on_hover_text(Hour::NAME);
+                ui.end_row();
+
+                ui.label("Days per year");
+                label_click_to_copy(ui, format!("{:.5}", calendar.days_per_year));
+                ui.end_row();
+
+                ui.label("Common year")
+                    .on_hover_text("Number of days in a year without leap days");
+                label_click_to_copy(ui, format!("{}", calendar.common_year_days));
+                ui.end_row();
+
+                ui.label("Remainder")
+                    .on_hover_text("Part of a day left over every year");
+                label_click_to_copy(ui, format!("{:.5}", calendar.remainder));
+                ui.end_row();
+
+                ui.label("Leap rules");
+                if calendar.leap_rules.is_empty() {
+                    ui.label("none needed");
+                } else {
+                    ui.vertical(|ui| {
+                        for rule in &calendar.leap_rules {
+                            label_click_to_copy(ui, rule.to_string());
+                        }
+                    });
+                }
+                ui.end_row();
+
+                ui.label("Average year")
+                    .on_hover_text("Including leap days");
+                label_click_to_copy(ui, format!("{:.5}", calendar.mean_year_days()));
+                ui.end_row();
+
+                ui.label("Drift after");
+                ui.add(
+                    egui::Slider::new(&mut self.input_drift_years, 1..=100_000)
+                        .logarithmic(true)
+                        .suffix(" years"),
+                );
+                label_click_to_copy(
+                    ui,
+                    format!("{:.3} days", calendar.drift_days(self.input_drift_years)),
+                );
+                ui.end_row();
+
+                if let (Some(days_per_month), Some(months_per_year)) =
+                    (calendar.days_per_month, calendar.months_per_year)
+                {
+                    ui.label("Days per month");
+                    label_click_to_copy(ui, format!("{:.3}", days_per_month));
+                    ui.end_row();
+
+                    ui.label("Months per year");
+                    label_click_to_copy(ui, format!("{:.3}", months_per_year));
+                    ui.end_row();
+                }
+            });
+
+        ui.label("Months")
+            .on_hover_text("Leap days go to the last month");
+        egui::Grid::new("calendar_months")
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                for month in &calendar.months {
+                    ui.label(&month.name);
+                    label_click_to_copy(ui, format!("{} days", month.days));
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+fn export_csv(calendar: &Calendar) -> std::io::Result<()> {
+    match rfd::FileDialog::new()
+        .add_filter("Comma separated values", &["csv"])
+        .set_file_name("calendar.csv")
+        .save_file()
+    {
+        Some(path) => std::fs::write(path, calendar.to_csv()),
+        None => Ok(()),
+    }
+}
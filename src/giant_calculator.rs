@@ -0,0 +1,131 @@
+use crate::orbit_calculator::OrbitCalculator;
+use crate::widgets::{label_click_to_copy, unit_value_click_to_copy};
+use eframe::egui;
+use eframe::egui::Ui;
+use worldsmith_lib::giant::GiantPlanet;
+use worldsmith_lib::orbit::Orbit;
+use worldsmith_lib::unit_system::UnitSystem;
+use worldsmith_lib::units::{Hour, JupiterMass, Unit};
+use worldsmith_lib::MainSequenceStar;
+
+pub struct GiantCalculator {
+    input_jupiter_mass: f32,
+    input_rotation_period: f32,
+    giant: GiantPlanet,
+    orbit_calculator: OrbitCalculator,
+}
+
+impl GiantCalculator {
+    pub fn new(star: &MainSequenceStar) -> Self {
+        // Starts out as jupiter.
+        let input_jupiter_mass = 1.0;
+        let input_rotation_period = 9.925;
+        let orbit_calculator = OrbitCalculator::new(star, 5.2, 0.0489, 1.3);
+
+        GiantCalculator {
+            input_jupiter_mass,
+            input_rotation_period,
+            giant: GiantPlanet::calculate_parameters(
+                input_jupiter_mass.into(),
+                input_rotation_period.into(),
+                star,
+                orbit_calculator.orbit().semi_major_axis,
+            ),
+            orbit_calculator,
+        }
+    }
+
+    pub fn giant(&self) -> &GiantPlanet {
+        &self.giant
+    }
+
+    pub fn orbit(&self) -> &Orbit {
+        self.orbit_calculator.orbit()
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, star: &MainSequenceStar, unit_system: UnitSystem) {
+        ui.heading("Giant calculator");
+
+        // From about the mass of uranus, up to the lightest brown dwarfs.
+        let giant_mass_range = 0.04..=13.0;
+
+        egui::Grid::new("giant_planet_parameters")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Mass");
+                ui.add(
+                    egui::Slider::new(&mut self.input_jupiter_mass, giant_mass_range)
+                        .logarithmic(true),
+                );
+                ui.label(JupiterMass::SYMBOL)
+                    .on_hover_text(JupiterMass::NAME);
+                ui.end_row();
+
+                ui.label("Rotation period");
+                ui.add(
+                    egui::Slider::new(&mut self.input_rotation_period, 3.0..=100.0)
+                        .logarithmic(true),
+                );
+                ui.label(Hour::SYMBOL).on_hover_text(Hour::NAME);
+                ui.end_row();
+
+                self.giant = GiantPlanet::calculate_parameters(
+                    self.input_jupiter_mass.into(),
+                    self.input_rotation_period.into(),
+                    star,
+                    self.orbit_calculator.orbit().semi_major_axis,
+                );
+                let giant = &self.giant;
+
+                ui.label("Kind");
+                label_click_to_copy(ui, format!("{}", giant.kind));
+                ui.end_row();
+
+                ui.label("Class").on_hover_text(
+                    "Sudarsky class, based on the kind of clouds that can form at this temperature",
+                );
+                label_click_to_copy(
+                    ui,
+                    format!("{} ({})", giant.class, giant.class.description()),
+                );
+                ui.end_row();
+
+                ui.label("Radius");
+                unit_value_click_to_copy(ui, giant.radius, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Equatorial radius");
+                unit_value_click_to_copy(ui, giant.equatorial_radius, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Polar radius");
+                unit_value_click_to_copy(ui, giant.polar_radius, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Oblateness")
+                    .on_hover_text("How much the planet is flattened by its rotation");
+                label_click_to_copy(ui, format!("{:.5}", giant.oblateness));
+                ui.end_row();
+
+                ui.label("Density");
+                unit_value_click_to_copy(ui, giant.density, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Gravity").on_hover_text("At the cloud tops");
+                unit_value_click_to_copy(ui, giant.surface_gravity, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Escape velocity");
+                unit_value_click_to_copy(ui, giant.escape_velocity, unit_system, 3);
+                ui.end_row();
+
+                ui.label("Equilibrium temperature")
+                    .on_hover_text("Temperature when only heated by the star");
+                unit_value_click_to_copy(ui, giant.equilibrium_temperature, unit_system, 0);
+                ui.end_row();
+            });
+
+        self.orbit_calculator.show(ui, star, unit_system);
+    }
+}
@@ -1,14 +1,37 @@
 #![deny(unsafe_code)]
 #![warn(rust_2018_idioms)]
 
+mod atmosphere_calculator;
+mod calendar_calculator;
+mod giant_calculator;
+mod orbit_calculator;
+mod orrery;
+mod planet_calculator;
+mod system_editor;
 mod widgets;
 
+use crate::calendar_calculator::CalendarCalculator;
 use crate::egui::Color32;
-use crate::widgets::{color_click_to_copy, label_click_to_copy, star_size_comparison_chart};
+use crate::giant_calculator::GiantCalculator;
+use crate::orrery::Orrery;
+use crate::planet_calculator::PlanetCalculator;
+use crate::system_editor::show_system_editor;
+use crate::widgets::{
+    color_click_to_copy, format_unit_value, habitable_zone_chart, hr_diagram, label_click_to_copy,
+    star_size_comparison_chart, to_color32, unit_label, unit_value_click_to_copy, HrDiagramStars,
+};
 use eframe::egui::{Context, Ui, Visuals};
 use eframe::{egui, Frame};
-use worldsmith_lib::units::{Kelvin, SolarDensity, SolarLuminosity, SolarMass, SolarRadius, Unit};
-use worldsmith_lib::MainSequenceStar;
+use std::path::PathBuf;
+use worldsmith_lib::generator::generate_system;
+use worldsmith_lib::project::Project;
+use worldsmith_lib::system::{StarSystem, SystemStar};
+use worldsmith_lib::unit_system::UnitSystem;
+use worldsmith_lib::units::{AstronomicalUnit, JupiterMass, SolarLuminosity, SolarMass, Unit};
+use worldsmith_lib::{
+    MainSequenceStar, STAR_MAX_AGE_GIGAYEARS, STAR_MAX_MASS_SOLAR, STAR_MIN_AGE_GIGAYEARS,
+    STAR_MIN_MASS_SOLAR,
+};
 
 fn main() {
     let native_options = eframe::NativeOptions::default();
@@ -19,43 +42,169 @@ fn main() {
     );
 }
 
+#[derive(PartialEq)]
+enum Tab {
+    Star,
+    Planet,
+    Giant,
+    Calendar,
+    System,
+}
+
 struct WorldSmith {
+    tab: Tab,
     input_solar_mass: f32,
     input_age_gigayears: f32,
     star: MainSequenceStar,
     saved_stars: Vec<MainSequenceStar>,
+    hr_diagram_stars: HrDiagramStars,
+    planet_calculator: PlanetCalculator,
+    giant_calculator: GiantCalculator,
+    calendar_calculator: CalendarCalculator,
+    orrery: Orrery,
+    system: StarSystem,
+    /// Units that the results are shown in.
+    unit_system: UnitSystem,
+    /// Seed for generating a random system.
+    input_seed: u64,
+    /// Where the project was last saved to, or loaded from.
+    project_path: Option<PathBuf>,
+    /// Shown to the user until they close it.
+    file_error: Option<String>,
 }
 
+/// File extension of project files.
+const PROJECT_EXTENSION: &str = "ron";
+
 impl WorldSmith {
     fn new(cc: &eframe::CreationContext<'_>) -> Self {
         cc.egui_ctx.set_visuals(Visuals::dark());
 
         let input_solar_mass = 1.0;
-        let input_age_gigayears = 100.0;
-        let star =
-            MainSequenceStar::calculate_parameters(input_solar_mass.into(), input_age_gigayears);
+        let input_age_gigayears = 4.6;
+        let star = MainSequenceStar::calculate_parameters(
+            input_solar_mass.into(),
+            input_age_gigayears.into(),
+        );
 
         WorldSmith {
+            tab: Tab::Star,
             input_solar_mass,
             input_age_gigayears,
-            star,
             saved_stars: vec![],
+            hr_diagram_stars: HrDiagramStars::new(),
+            planet_calculator: PlanetCalculator::new(&star),
+            giant_calculator: GiantCalculator::new(&star),
+            calendar_calculator: CalendarCalculator::new(),
+            orrery: Orrery::new(),
+            system: StarSystem::new("New system", SystemStar::new("Primary", star.clone())),
+            star,
+            unit_system: UnitSystem::default(),
+            input_seed: 0,
+            project_path: None,
+            file_error: None,
+        }
+    }
+
+    fn open_project(&mut self) {
+        let path = match rfd::FileDialog::new()
+            .add_filter("Worldsmith project", &[PROJECT_EXTENSION])
+            .pick_file()
+        {
+            Some(path) => path,
+            None => return,
+        };
+
+        match Project::load(&path) {
+            Ok(project) => {
+                self.system = project.system;
+                self.saved_stars = project.saved_stars;
+                self.project_path = Some(path);
+            }
+            Err(error) => self.file_error = Some(error.to_string()),
+        }
+    }
+
+    /// Saves to the file the project was last saved to or loaded from.
+    /// Asks for a file if there is none yet.
+    fn save_project(&mut self) {
+        match self.project_path.clone() {
+            Some(path) => self.save_project_to(path),
+            None => self.save_project_as(),
+        }
+    }
+
+    fn save_project_as(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Worldsmith project", &[PROJECT_EXTENSION])
+            .set_file_name(&format!("{}.{}", self.system.name, PROJECT_EXTENSION))
+            .save_file()
+        {
+            self.save_project_to(path);
+        }
+    }
+
+    fn save_project_to(&mut self, path: PathBuf) {
+        let project = Project::new(self.system.clone(), self.saved_stars.clone());
+
+        match project.save(&path) {
+            Ok(()) => self.project_path = Some(path),
+            Err(error) => self.file_error = Some(error.to_string()),
+        }
+    }
+
+    fn show_file_menu(&mut self, ui: &mut Ui) {
+        ui.menu_button("File", |ui| {
+            if ui.button("Open...").clicked() {
+                ui.close_menu();
+                self.open_project();
+            }
+            if ui.button("Save").clicked() {
+                ui.close_menu();
+                self.save_project();
+            }
+            if ui.button("Save As...").clicked() {
+                ui.close_menu();
+                self.save_project_as();
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut self.input_seed))
+                    .on_hover_text("The same seed always gives the same system");
+                if ui.button("New random system").clicked() {
+                    ui.close_menu();
+                    self.system = generate_system(self.input_seed);
+                    // So that saving doesn't overwrite the previous project.
+                    self.project_path = None;
+                }
+            });
+        });
+    }
+
+    fn show_file_error(&mut self, ctx: &Context) {
+        let mut open = self.file_error.is_some();
+
+        if let Some(error) = &self.file_error {
+            egui::Window::new("Error")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(error);
+                });
+        }
+
+        if !open {
+            self.file_error = None;
         }
     }
 
     fn show_star_calculator_display(&mut self, ui: &mut Ui) {
         ui.heading("Star calculator");
 
-        // TODO (Wybe 2022-07-03): Encode this in the MainSequenceStar struct?
-        let main_sequence_mass_range = 0.075..=94.0;
-        // TODO: How large should this range be?
-        let main_sequence_age_range = 10.0..=1000.0;
-
-        let color = Color32::from_rgb(
-            self.star.color.r(),
-            self.star.color.g(),
-            self.star.color.b(),
-        );
+        let mass_range = STAR_MIN_MASS_SOLAR..=STAR_MAX_MASS_SOLAR;
+        let main_sequence_age_range = STAR_MIN_AGE_GIGAYEARS..=STAR_MAX_AGE_GIGAYEARS;
 
         egui::Grid::new("main_sequence_parameters")
             .num_columns(3)
@@ -65,10 +214,7 @@ impl WorldSmith {
                 let previous_age = self.input_age_gigayears;
 
                 ui.label("Mass");
-                ui.add(
-                    egui::Slider::new(&mut self.input_solar_mass, main_sequence_mass_range)
-                        .logarithmic(true),
-                );
+                ui.add(egui::Slider::new(&mut self.input_solar_mass, mass_range).logarithmic(true));
                 ui.label(SolarMass::SYMBOL).on_hover_text(SolarMass::NAME);
                 ui.end_row();
 
@@ -86,82 +232,158 @@ impl WorldSmith {
                 {
                     self.star = MainSequenceStar::calculate_parameters(
                         self.input_solar_mass.into(),
-                        self.input_age_gigayears,
+                        self.input_age_gigayears.into(),
                     );
                 }
 
                 let star = &self.star;
 
                 ui.label("Stellar class");
-                label_click_to_copy(ui, format!("{}", star.class));
+                label_click_to_copy(ui, star.classification());
                 // TODO (Wybe 2022-07-03): Add explanation of stellar class here.
                 ui.end_row();
 
+                ui.label("Stage");
+                label_click_to_copy(ui, format!("{}", star.stage));
+                ui.end_row();
+
+                if star.is_brown_dwarf() {
+                    ui.label("Mass");
+                    label_click_to_copy(ui, format!("{:.1}", JupiterMass::from(star.mass)));
+                    ui.label(JupiterMass::SYMBOL)
+                        .on_hover_text(JupiterMass::NAME);
+                    ui.end_row();
+                }
+
+                if star.current_mass != star.mass {
+                    ui.label("Current mass")
+                        .on_hover_text("Stars lose mass at the end of their life");
+                    unit_value_click_to_copy(ui, star.current_mass, self.unit_system, 3);
+                    ui.end_row();
+                }
+
                 // TODO (Wybe 2022-07-03): Allow copying by clicking a value (and change cursor to indicate you can click).
                 // TODO (Wybe 2022-07-03): Add a button to copy everything into the clipboard.
-                ui.label("Maximum age");
-                label_click_to_copy(ui, format!("{:.5}", star.max_age_gigayears));
-                ui.label("Gyr")
-                    .on_hover_text("Giga earth years. 1 billion years (1,000,000,000)");
-                ui.end_row();
+                // Brown dwarfs never reach the main sequence, so they have no maximum age.
+                if !star.is_brown_dwarf() {
+                    ui.label("Maximum age");
+                    label_click_to_copy(ui, format!("{:.5}", star.max_age_gigayears));
+                    ui.label("Gyr")
+                        .on_hover_text("Giga earth years. 1 billion years (1,000,000,000)");
+                    ui.end_row();
+
+                    ui.label("Lifetime elapsed");
+                    label_click_to_copy(ui, format!("{:.1}", star.lifetime_fraction * 100.));
+                    ui.label("%")
+                        .on_hover_text("Percentage of the main sequence lifetime that has passed");
+                    ui.end_row();
+                }
 
                 ui.label("Radius");
-                label_click_to_copy(ui, format!("{:.5}", star.radius));
-                ui.label(SolarRadius::SYMBOL)
-                    .on_hover_text(SolarRadius::NAME);
+                unit_value_click_to_copy(ui, star.radius, self.unit_system, 5);
                 ui.end_row();
 
                 ui.label("Luminosity");
-                label_click_to_copy(
-                    ui,
-                    &if star.luminosity < SolarLuminosity::new(1000.) {
-                        format!("{:.5}", star.luminosity)
-                    } else {
-                        format!("{:.0}", star.luminosity)
-                    },
-                );
-                ui.label(SolarLuminosity::SYMBOL)
-                    .on_hover_text(SolarLuminosity::NAME);
+                let decimals = if star.luminosity < SolarLuminosity::new(1000.) {
+                    5
+                } else {
+                    0
+                };
+                unit_value_click_to_copy(ui, star.luminosity, self.unit_system, decimals);
                 ui.end_row();
 
                 ui.label("Density");
-                label_click_to_copy(ui, format!("{:.5}", star.density));
-                ui.label(SolarDensity::SYMBOL)
-                    .on_hover_text(SolarDensity::NAME);
+                unit_value_click_to_copy(ui, star.density, self.unit_system, 5);
                 ui.end_row();
 
                 ui.label("Temperature");
-                label_click_to_copy(ui, format!("{:.0}", star.temperature));
-                ui.label(Kelvin::SYMBOL).on_hover_text(Kelvin::NAME);
+                unit_value_click_to_copy(ui, star.temperature, self.unit_system, 0);
+                ui.end_row();
+
+                ui.label("Color").on_hover_text(
+                    "The color of the star, made more saturated so that stars are easier to tell apart",
+                );
+                color_click_to_copy(ui, to_color32(&star.color));
                 ui.end_row();
 
-                // TODO WYBE: Add info on exactly what this color means.
-                ui.label("Color");
-                color_click_to_copy(ui, color);
+                ui.label("Perceived color").on_hover_text(
+                    "The color of the star as it would look to the naked eye. \
+                    Most stars look nearly white",
+                );
+                color_click_to_copy(ui, to_color32(&star.perceived_color));
+                ui.end_row();
+
+                ui.label("Habitable zone");
+                label_click_to_copy(
+                    ui,
+                    format!(
+                        "{} - {}",
+                        format_unit_value(star.habitable_zone_inner_edge, self.unit_system, 3),
+                        format_unit_value(star.habitable_zone_outer_edge, self.unit_system, 3)
+                    ),
+                );
+                unit_label::<AstronomicalUnit>(ui, self.unit_system);
+                ui.end_row();
+
+                ui.label("Frost line");
+                unit_value_click_to_copy(ui, star.frost_line, self.unit_system, 3);
+                ui.end_row();
+
+                ui.label("Planetary system limits")
+                    .on_hover_text("Planets can only form between these distances");
+                label_click_to_copy(
+                    ui,
+                    format!(
+                        "{} - {}",
+                        format_unit_value(star.inner_system_limit, self.unit_system, 3),
+                        format_unit_value(star.outer_system_limit, self.unit_system, 3)
+                    ),
+                );
+                unit_label::<AstronomicalUnit>(ui, self.unit_system);
+                ui.end_row();
             });
 
-        star_size_comparison_chart(ui, self.star.radius);
+        if self.star.is_past_main_sequence() {
+            ui.colored_label(
+                Color32::from_rgb(230, 150, 0),
+                format!(
+                    "⚠ This star is older than its main sequence lifetime. It is now a {}.",
+                    self.star.stage
+                ),
+            );
+        }
 
-        if ui.button("Save").clicked() {
-            self.saved_stars.push(self.star.clone());
+        star_size_comparison_chart(ui, self.star.radius);
+        habitable_zone_chart(ui, &self.star);
+        if let Some(clicked) = hr_diagram(
+            ui,
+            &self.star,
+            &self.saved_stars,
+            &self.hr_diagram_stars,
+            self.unit_system,
+        ) {
+            self.input_solar_mass = clicked.mass.into();
+            self.input_age_gigayears = clicked.age_gigayears.into();
+            self.star = clicked;
         }
 
-        // todo: add a habitable zone comparison chart,
-        //       showing where the orbits of the planets in the solar system are relative to
-        //       the "habitable" zone of this star. Including how long the year lengths would be.
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                self.saved_stars.push(self.star.clone());
+            }
+            if ui.button("Add to system").clicked() {
+                let name = format!("Star {}", self.system.stars().len() + 1);
+                self.system
+                    .add_star(SystemStar::new(name, self.star.clone()));
+            }
+        });
     }
 
     fn show_saved_stars(&mut self, ui: &mut Ui) {
         egui::Grid::new("saved_stars").striped(true).show(ui, |ui| {
             for star in self.saved_stars.iter() {
-                let color = Color32::from_rgb(
-                    self.star.color.r(),
-                    self.star.color.g(),
-                    self.star.color.b(),
-                );
-
-                label_click_to_copy(ui, format!("{}", star.class));
-                color_click_to_copy(ui, color);
+                label_click_to_copy(ui, star.classification());
+                color_click_to_copy(ui, to_color32(&star.color));
                 ui.end_row();
             }
         });
@@ -169,18 +391,93 @@ impl WorldSmith {
 }
 
 impl eframe::App for WorldSmith {
-    fn update(&mut self, ctx: &Context, frame: &mut Frame) {
+    fn update(&mut self, ctx: &Context, _frame: &mut Frame) {
         egui::TopBottomPanel::top("toolbar").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 global_dark_light_mode_switch(ui);
                 ui.separator();
+                self.show_file_menu(ui);
+                ui.separator();
+                ui.selectable_value(&mut self.tab, Tab::Star, "Star");
+                ui.selectable_value(&mut self.tab, Tab::Planet, "Planet");
+                ui.selectable_value(&mut self.tab, Tab::Giant, "Giant");
+                ui.selectable_value(&mut self.tab, Tab::Calendar, "Calendar");
+                ui.selectable_value(&mut self.tab, Tab::System, "System");
+                ui.separator();
+                egui::ComboBox::from_label("Units")
+                    .selected_text(self.unit_system.to_string())
+                    .show_ui(ui, |ui| {
+                        for unit_system in UnitSystem::ALL {
+                            ui.selectable_value(
+                                &mut self.unit_system,
+                                unit_system,
+                                unit_system.to_string(),
+                            );
+                        }
+                    });
             });
         });
 
-        egui::CentralPanel::default().show(ctx, |ui| {
-            self.show_star_calculator_display(ui);
-            self.show_saved_stars(ui);
+        egui::CentralPanel::default().show(ctx, |ui| match self.tab {
+            Tab::Star => {
+                self.show_star_calculator_display(ui);
+                self.show_saved_stars(ui);
+            }
+            Tab::Planet => {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    self.planet_calculator
+                        .show(ui, &self.star, self.unit_system);
+
+                    if ui
+                        .button("Add to system")
+                        .on_hover_text("Adds this planet to the system, orbiting the primary star")
+                        .clicked()
+                    {
+                        let name = format!("Planet {}", self.system.planets.len() + 1);
+                        self.system.add_planet(
+                            name,
+                            0,
+                            self.planet_calculator.planet().clone(),
+                            self.planet_calculator.orbit(),
+                            self.planet_calculator.axial_tilt(),
+                        );
+                    }
+                });
+            }
+            Tab::Giant => {
+                self.giant_calculator.show(ui, &self.star, self.unit_system);
+
+                if ui
+                    .button("Add to system")
+                    .on_hover_text("Adds this giant to the system, orbiting the primary star")
+                    .clicked()
+                {
+                    let name = format!("Giant {}", self.system.giants.len() + 1);
+                    self.system.add_giant(
+                        name,
+                        0,
+                        self.giant_calculator.giant(),
+                        self.giant_calculator.orbit(),
+                    );
+                }
+            }
+            Tab::Calendar => {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    self.calendar_calculator
+                        .show(ui, self.planet_calculator.orbit());
+                });
+            }
+            Tab::System => {
+                ui.columns(2, |columns| {
+                    egui::ScrollArea::vertical().show(&mut columns[0], |ui| {
+                        show_system_editor(ui, &mut self.system, &self.star, self.unit_system);
+                    });
+                    self.orrery.show(&mut columns[1], &self.system);
+                });
+            }
         });
+
+        self.show_file_error(ctx);
     }
 }
 
@@ -0,0 +1,127 @@
+use crate::widgets::{
+    format_unit_value, label_click_to_copy, unit_label, unit_value_click_to_copy,
+};
+use eframe::egui;
+use eframe::egui::Ui;
+use worldsmith_lib::orbit::Orbit;
+use worldsmith_lib::unit_system::UnitSystem;
+use worldsmith_lib::units::{
+    AstronomicalUnit, Degree, EarthDay, EarthInsolation, EarthYear, KilometerPerSecond, Unit,
+};
+use worldsmith_lib::MainSequenceStar;
+
+/// Inputs and results of an orbit around the star from the star calculator.
+/// Used by the planet calculators.
+pub struct OrbitCalculator {
+    input_semi_major_axis: f32,
+    input_eccentricity: f32,
+    input_inclination: f32,
+    orbit: Orbit,
+}
+
+impl OrbitCalculator {
+    pub fn new(
+        star: &MainSequenceStar,
+        semi_major_axis: f32,
+        eccentricity: f32,
+        inclination: f32,
+    ) -> Self {
+        OrbitCalculator {
+            input_semi_major_axis: semi_major_axis,
+            input_eccentricity: eccentricity,
+            input_inclination: inclination,
+            orbit: Orbit::calculate_parameters(
+                star,
+                semi_major_axis.into(),
+                eccentricity,
+                inclination.into(),
+            ),
+        }
+    }
+
+    pub fn orbit(&self) -> &Orbit {
+        &self.orbit
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, star: &MainSequenceStar, unit_system: UnitSystem) {
+        ui.heading("Orbit");
+        ui.label(format!(
+            "Around the {} star from the star calculator",
+            star.classification()
+        ));
+
+        let semi_major_axis_range =
+            f32::from(star.inner_system_limit)..=f32::from(star.outer_system_limit);
+
+        egui::Grid::new("orbit_parameters")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Semi-major axis");
+                ui.add(
+                    egui::Slider::new(&mut self.input_semi_major_axis, semi_major_axis_range)
+                        .logarithmic(true),
+                );
+                ui.label(AstronomicalUnit::SYMBOL)
+                    .on_hover_text(AstronomicalUnit::NAME);
+                ui.end_row();
+
+                ui.label("Eccentricity");
+                ui.add(egui::Slider::new(&mut self.input_eccentricity, 0.0..=0.99));
+                ui.end_row();
+
+                ui.label("Inclination");
+                ui.add(egui::Slider::new(&mut self.input_inclination, 0.0..=180.0));
+                ui.label(Degree::SYMBOL).on_hover_text(Degree::NAME);
+                ui.end_row();
+
+                // The star can be changed in the star calculator at any time,
+                // so the orbit is always recalculated. This is cheap enough.
+                self.orbit = Orbit::calculate_parameters(
+                    star,
+                    self.input_semi_major_axis.into(),
+                    self.input_eccentricity,
+                    self.input_inclination.into(),
+                );
+                let orbit = &self.orbit;
+
+                ui.label("Period");
+                label_click_to_copy(ui, format!("{:.5}", orbit.period));
+                ui.label(EarthYear::SYMBOL).on_hover_text(EarthYear::NAME);
+                ui.end_row();
+
+                ui.label("");
+                label_click_to_copy(ui, format!("{:.2}", orbit.period_days));
+                ui.label(EarthDay::SYMBOL).on_hover_text(EarthDay::NAME);
+                ui.end_row();
+
+                ui.label("Periapsis");
+                unit_value_click_to_copy(ui, orbit.periapsis, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Apoapsis");
+                unit_value_click_to_copy(ui, orbit.apoapsis, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Orbital velocity")
+                    .on_hover_text("Mean (slowest at apoapsis - fastest at periapsis)");
+                label_click_to_copy(
+                    ui,
+                    format!(
+                        "{} ({} - {})",
+                        format_unit_value(orbit.mean_velocity, unit_system, 3),
+                        format_unit_value(orbit.apoapsis_velocity, unit_system, 3),
+                        format_unit_value(orbit.periapsis_velocity, unit_system, 3)
+                    ),
+                );
+                unit_label::<KilometerPerSecond>(ui, unit_system);
+                ui.end_row();
+
+                ui.label("Insolation");
+                label_click_to_copy(ui, format!("{:.5}", orbit.insolation));
+                ui.label(EarthInsolation::SYMBOL)
+                    .on_hover_text(EarthInsolation::NAME);
+                ui.end_row();
+            });
+    }
+}
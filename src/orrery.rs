@@ -0,0 +1,242 @@
+use crate::widgets::to_color32;
+use eframe::egui;
+use eframe::egui::{Align2, Color32, FontFamily, FontId, Pos2, Sense, Shape, Stroke, Ui, Vec2};
+use std::f32::consts::TAU;
+use worldsmith_lib::orbit::Orbit;
+use worldsmith_lib::system::StarSystem;
+use worldsmith_lib::units::{AstronomicalUnit, EarthYear, Unit};
+
+/// Points per orbit when drawing it.
+const ORBIT_SEGMENTS: usize = 128;
+/// Orbits don't have an orientation in the model. They are turned by this much (in radians)
+/// compared to the previous one, so that eccentric orbits don't all point the same way.
+const ORBIT_ORIENTATION_STEP: f32 = 2.4;
+
+/// Draws one star of a system from above, with everything that orbits it.
+pub struct Orrery {
+    /// Index of the star in the center.
+    star: usize,
+    /// In earth years.
+    time: f32,
+    playing: bool,
+    /// Earth years that pass per second while playing.
+    speed: f32,
+    logarithmic: bool,
+    /// Pixels per AU in linear mode. In logarithmic mode, 1 is the whole system.
+    zoom: f32,
+    /// Offset of the star from the center of the view, in pixels.
+    pan: Vec2,
+}
+
+impl Orrery {
+    pub fn new() -> Self {
+        Orrery {
+            star: 0,
+            time: 0.,
+            playing: false,
+            speed: 0.5,
+            logarithmic: true,
+            zoom: 1.,
+            pan: Vec2::ZERO,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, system: &StarSystem) {
+        ui.heading("Orrery");
+
+        let stars = system.stars();
+        self.star = self.star.min(stars.len() - 1);
+
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("orrery_star")
+                .selected_text(&stars[self.star].name)
+                .show_ui(ui, |ui| {
+                    for (index, star) in stars.iter().enumerate() {
+                        ui.selectable_value(&mut self.star, index, &star.name);
+                    }
+                });
+
+            if ui
+                .checkbox(&mut self.logarithmic, "Logarithmic")
+                .on_hover_text("Makes both close and far orbits visible at the same time")
+                .changed()
+            {
+                self.reset_view();
+            }
+            if ui.button("Reset view").clicked() {
+                self.reset_view();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let play_label = if self.playing { "⏸" } else { "▶" };
+            if ui.button(play_label).clicked() {
+                self.playing = !self.playing;
+            }
+            ui.add(
+                egui::Slider::new(&mut self.time, 0.0..=1000.0)
+                    .logarithmic(true)
+                    .text(EarthYear::SYMBOL),
+            );
+            ui.add(
+                egui::Slider::new(&mut self.speed, 0.01..=100.0)
+                    .logarithmic(true)
+                    .text(format!("{} per second", EarthYear::SYMBOL)),
+            );
+        });
+
+        if self.playing {
+            self.time += ui.input().unstable_dt * self.speed;
+            ui.ctx().request_repaint();
+        }
+
+        self.show_view(ui, system);
+    }
+
+    fn reset_view(&mut self) {
+        self.zoom = 1.;
+        self.pan = Vec2::ZERO;
+    }
+
+    fn show_view(&mut self, ui: &mut Ui, system: &StarSystem) {
+        let widget_size = Vec2::new(500.0, 500.0);
+        let (response, painter) = ui.allocate_painter(widget_size, Sense::drag());
+        let rect = response.rect;
+
+        self.pan += response.drag_delta();
+        if response.hovered() {
+            let scroll = ui.input().scroll_delta.y;
+            self.zoom *= (scroll / 200.).exp();
+        }
+
+        let star = &system.stars()[self.star].star;
+        let center = rect.center() + self.pan;
+
+        // The whole system fits in the view when not zoomed in.
+        let min_distance = f32::from(star.inner_system_limit) * 0.5;
+        let max_distance = f32::from(star.outer_system_limit);
+        let view_radius = rect.width().min(rect.height()) / 2.;
+        let logarithmic = self.logarithmic;
+        let zoom = self.zoom;
+        let to_pixels = |distance: f32| {
+            if logarithmic {
+                let fraction = (distance.max(min_distance) / min_distance).log10()
+                    / (max_distance / min_distance).log10();
+                fraction * view_radius * zoom
+            } else {
+                distance / max_distance * view_radius * zoom
+            }
+        };
+        let to_screen = |distance: f32, angle: f32| {
+            center + to_pixels(distance) * Vec2::new(angle.cos(), -angle.sin())
+        };
+
+        if !ui.is_rect_visible(rect) {
+            return;
+        }
+        let visuals = ui.style().interact(&response);
+
+        // This is drawn directly on the ui painter, instead of the
+        // painter returned by `allocate_painter`, so that the border is not clipped.
+        ui.painter().rect(
+            rect.expand(visuals.expansion),
+            visuals.rounding,
+            Color32::from_rgb(5, 5, 15),
+            visuals.bg_stroke,
+        );
+
+        // Shaded rings, drawn as a thick circle between the two edges.
+        let annulus = |inner: AstronomicalUnit, outer: AstronomicalUnit, color: Color32| {
+            let inner = to_pixels(inner.into());
+            let outer = to_pixels(outer.into());
+            painter.circle_stroke(
+                center,
+                (inner + outer) / 2.,
+                Stroke::new(outer - inner, color),
+            );
+        };
+        annulus(
+            star.habitable_zone_inner_edge,
+            star.habitable_zone_outer_edge,
+            Color32::from_rgb(40, 140, 40).linear_multiply(0.4),
+        );
+        for belt in system.belts.iter().filter(|b| b.host_star == self.star) {
+            annulus(
+                belt.inner_edge,
+                belt.outer_edge,
+                Color32::from_gray(120).linear_multiply(0.4),
+            );
+        }
+        painter.circle_stroke(
+            center,
+            to_pixels(star.frost_line.into()),
+            Stroke::new(1.0, Color32::from_rgb(150, 200, 255)),
+        );
+
+        let bodies = system
+            .planets
+            .iter()
+            .filter(|p| p.host_star == self.star)
+            .map(|p| (&p.name, &p.orbit, Color32::from_rgb(100, 160, 255), 4.0))
+            .chain(
+                system
+                    .giants
+                    .iter()
+                    .filter(|g| g.host_star == self.star)
+                    .map(|g| (&g.name, &g.orbit, Color32::from_rgb(230, 180, 120), 6.0)),
+            );
+
+        for (index, (name, orbit, color, size)) in bodies.enumerate() {
+            let orientation = index as f32 * ORBIT_ORIENTATION_STEP;
+
+            let points: Vec<Pos2> = (0..=ORBIT_SEGMENTS)
+                .map(|step| {
+                    let true_anomaly = step as f32 / ORBIT_SEGMENTS as f32 * TAU;
+                    to_screen(
+                        calculate_distance(orbit, true_anomaly),
+                        true_anomaly + orientation,
+                    )
+                })
+                .collect();
+            painter.add(Shape::line(
+                points,
+                Stroke::new(1.0, color.linear_multiply(0.5)),
+            ));
+
+            let (true_anomaly, distance) = orbit.position_at(self.time / f32::from(orbit.period));
+            let position = to_screen(distance.into(), true_anomaly + orientation);
+            painter.circle_filled(position, size, color);
+            painter.text(
+                position + Vec2::new(size + 2., 0.),
+                Align2::LEFT_CENTER,
+                name,
+                FontId::new(12., FontFamily::Proportional),
+                Color32::WHITE,
+            );
+        }
+
+        painter.circle_filled(center, 8.0, to_color32(&star.color));
+
+        painter.text(
+            rect.left_bottom(),
+            Align2::LEFT_BOTTOM,
+            if self.logarithmic {
+                "logarithmic scale, scroll to zoom, drag to move".to_string()
+            } else {
+                format!(
+                    "{:.2} {} across, scroll to zoom, drag to move",
+                    2. * max_distance / self.zoom,
+                    AstronomicalUnit::SYMBOL
+                )
+            },
+            FontId::new(12., FontFamily::Proportional),
+            Color32::GRAY,
+        );
+    }
+}
+
+/// Distance to the star at the given angle from periapsis, in AU.
+fn calculate_distance(orbit: &Orbit, true_anomaly: f32) -> f32 {
+    let e = orbit.eccentricity;
+    f32::from(orbit.semi_major_axis) * (1. - e.powi(2)) / (1. + e * true_anomaly.cos())
+}
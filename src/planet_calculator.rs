@@ -0,0 +1,399 @@
+use crate::atmosphere_calculator::AtmosphereCalculator;
+use crate::orbit_calculator::OrbitCalculator;
+use crate::widgets::{
+    format_unit_value, label_click_to_copy, seasons_heatmap, unit_value_click_to_copy,
+    InsolationGrid,
+};
+use eframe::egui;
+use eframe::egui::{Color32, Ui};
+use worldsmith_lib::atmosphere::{calculate_exospheric_temperature, calculate_gas_retention};
+use worldsmith_lib::climate::{Climate, TemperatureVerdict};
+use worldsmith_lib::orbit::Orbit;
+use worldsmith_lib::planet::{PlanetComposition, TerrestrialPlanet};
+use worldsmith_lib::seasons::Seasons;
+use worldsmith_lib::tidal::{TidalLocking, TYPICAL_INITIAL_ROTATION_PERIOD_HOURS};
+use worldsmith_lib::unit_system::{ConvertUnit, UnitSystem};
+use worldsmith_lib::units::{Celsius, Degree, EarthMass, Hour, Kelvin, KilometerPerSecond, Unit};
+use worldsmith_lib::MainSequenceStar;
+
+pub struct PlanetCalculator {
+    input_earth_mass: f32,
+    input_iron_fraction: f32,
+    input_silicate_fraction: f32,
+    input_water_fraction: f32,
+    input_albedo: f32,
+    input_greenhouse_effect: f32,
+    input_initial_rotation_period: f32,
+    input_axial_tilt: f32,
+    /// Fraction of the year since periapsis, for the seasons.
+    input_time_of_year: f32,
+    insolation_grid: InsolationGrid,
+    planet: TerrestrialPlanet,
+    orbit_calculator: OrbitCalculator,
+    climate: Climate,
+    atmosphere_calculator: AtmosphereCalculator,
+}
+
+impl PlanetCalculator {
+    pub fn new(star: &MainSequenceStar) -> Self {
+        let composition = PlanetComposition::earth_like();
+        let input_earth_mass = 1.0;
+        let input_albedo = 0.3;
+        let input_greenhouse_effect = 1.0;
+        // The orbit of the earth.
+        let orbit_calculator = OrbitCalculator::new(star, 1.0, 0.0167, 0.0);
+        let planet =
+            TerrestrialPlanet::calculate_parameters(input_earth_mass.into(), composition.clone());
+        let climate = Climate::calculate_parameters(
+            star,
+            orbit_calculator.orbit(),
+            input_albedo,
+            input_greenhouse_effect,
+        );
+
+        PlanetCalculator {
+            input_earth_mass,
+            input_iron_fraction: composition.iron(),
+            input_silicate_fraction: composition.silicate(),
+            input_water_fraction: composition.water(),
+            input_albedo,
+            input_greenhouse_effect,
+            input_initial_rotation_period: TYPICAL_INITIAL_ROTATION_PERIOD_HOURS,
+            // The tilt of the earth.
+            input_axial_tilt: 23.44,
+            // Around the 21st of june, northern summer on the earth.
+            input_time_of_year: 0.47,
+            insolation_grid: InsolationGrid::new(),
+            atmosphere_calculator: AtmosphereCalculator::new(&planet, climate.surface_temperature),
+            planet,
+            climate,
+            orbit_calculator,
+        }
+    }
+
+    pub fn planet(&self) -> &TerrestrialPlanet {
+        &self.planet
+    }
+
+    pub fn orbit(&self) -> &Orbit {
+        self.orbit_calculator.orbit()
+    }
+
+    pub fn axial_tilt(&self) -> Degree {
+        self.input_axial_tilt.into()
+    }
+
+    pub fn show(&mut self, ui: &mut Ui, star: &MainSequenceStar, unit_system: UnitSystem) {
+        ui.heading("Planet calculator");
+
+        // The mass-radius relations are only fitted to this range.
+        let terrestrial_mass_range = 0.01..=10.0;
+
+        egui::Grid::new("terrestrial_planet_parameters")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                let previous_inputs = (
+                    self.input_earth_mass,
+                    self.input_iron_fraction,
+                    self.input_silicate_fraction,
+                    self.input_water_fraction,
+                );
+
+                ui.label("Mass");
+                ui.add(
+                    egui::Slider::new(&mut self.input_earth_mass, terrestrial_mass_range)
+                        .logarithmic(true),
+                );
+                ui.label(EarthMass::SYMBOL).on_hover_text(EarthMass::NAME);
+                ui.end_row();
+
+                ui.label("Iron");
+                ui.add(egui::Slider::new(&mut self.input_iron_fraction, 0.0..=1.0));
+                ui.label("")
+                    .on_hover_text("Relative amount of iron, mostly in the core");
+                ui.end_row();
+
+                ui.label("Silicate");
+                ui.add(egui::Slider::new(
+                    &mut self.input_silicate_fraction,
+                    0.0..=1.0,
+                ));
+                ui.label("")
+                    .on_hover_text("Relative amount of silicate rock, mostly in the mantle");
+                ui.end_row();
+
+                ui.label("Water");
+                ui.add(egui::Slider::new(&mut self.input_water_fraction, 0.0..=1.0));
+                ui.label("")
+                    .on_hover_text("Relative amount of water, as oceans or ice");
+                ui.end_row();
+
+                if previous_inputs
+                    != (
+                        self.input_earth_mass,
+                        self.input_iron_fraction,
+                        self.input_silicate_fraction,
+                        self.input_water_fraction,
+                    )
+                {
+                    self.planet = TerrestrialPlanet::calculate_parameters(
+                        self.input_earth_mass.into(),
+                        PlanetComposition::new(
+                            self.input_iron_fraction,
+                            self.input_silicate_fraction,
+                            self.input_water_fraction,
+                        ),
+                    );
+                }
+
+                let planet = &self.planet;
+
+                ui.label("Composition");
+                label_click_to_copy(
+                    ui,
+                    format!(
+                        "{:.0}% iron, {:.0}% silicate, {:.0}% water",
+                        planet.composition.iron() * 100.,
+                        planet.composition.silicate() * 100.,
+                        planet.composition.water() * 100.,
+                    ),
+                );
+                ui.end_row();
+
+                ui.label("Radius");
+                unit_value_click_to_copy(ui, planet.radius, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Density");
+                unit_value_click_to_copy(ui, planet.density, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Surface gravity");
+                unit_value_click_to_copy(ui, planet.surface_gravity, unit_system, 5);
+                ui.end_row();
+
+                ui.label("Escape velocity");
+                unit_value_click_to_copy(ui, planet.escape_velocity, unit_system, 3);
+                ui.end_row();
+
+                ui.label("Surface area");
+                unit_value_click_to_copy(ui, planet.surface_area, unit_system, 0);
+                ui.end_row();
+            });
+
+        self.orbit_calculator.show(ui, star, unit_system);
+
+        self.show_rotation(ui, star);
+
+        self.show_seasons(ui, star);
+
+        self.show_climate(ui, star, unit_system);
+
+        self.show_gas_retention(ui, unit_system);
+
+        self.atmosphere_calculator.show(
+            ui,
+            &self.planet,
+            self.climate.surface_temperature,
+            unit_system,
+        );
+    }
+
+    fn show_gas_retention(&self, ui: &mut Ui, unit_system: UnitSystem) {
+        ui.heading("Atmosphere");
+
+        let exospheric_temperature =
+            calculate_exospheric_temperature(self.orbit_calculator.orbit().insolation);
+        ui.label(format!(
+            "Gases that the planet keeps over billions of years, with an exosphere of {} {}",
+            format_unit_value(exospheric_temperature, unit_system, 0),
+            Kelvin::conversion(unit_system).symbol
+        ));
+
+        egui::Grid::new("gas_retention")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Gas");
+                ui.label("Molecule speed")
+                    .on_hover_text("Average speed of the molecules in the exosphere");
+                ui.label("Retained");
+                ui.end_row();
+
+                for retention in
+                    calculate_gas_retention(self.planet.escape_velocity, exospheric_temperature)
+                {
+                    ui.label(format!("{} ({})", retention.gas, retention.gas.formula()));
+                    label_click_to_copy(
+                        ui,
+                        format!(
+                            "{} {}",
+                            format_unit_value(retention.thermal_velocity, unit_system, 3),
+                            KilometerPerSecond::conversion(unit_system).symbol
+                        ),
+                    );
+                    if retention.retained {
+                        ui.colored_label(Color32::from_rgb(0, 180, 0), "yes");
+                    } else {
+                        ui.colored_label(Color32::from_rgb(230, 80, 0), "no");
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
+    fn show_rotation(&mut self, ui: &mut Ui, star: &MainSequenceStar) {
+        ui.heading("Rotation");
+
+        egui::Grid::new("rotation_parameters")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Initial rotation period")
+                    .on_hover_text("Length of a day when the planet formed");
+                ui.add(
+                    egui::Slider::new(&mut self.input_initial_rotation_period, 2.0..=1000.0)
+                        .logarithmic(true),
+                );
+                ui.label(Hour::SYMBOL).on_hover_text(Hour::NAME);
+                ui.end_row();
+
+                ui.label("Axial tilt")
+                    .on_hover_text("Angle between the rotation axis and the orbit");
+                ui.add(egui::Slider::new(&mut self.input_axial_tilt, 0.0..=180.0));
+                ui.label(Degree::SYMBOL).on_hover_text(Degree::NAME);
+                ui.end_row();
+
+                let locking = TidalLocking::calculate_for_planet(
+                    &self.planet,
+                    self.input_initial_rotation_period.into(),
+                    star,
+                    self.orbit_calculator.orbit(),
+                );
+
+                ui.label("Tidal locking time")
+                    .on_hover_text("Time until the tides of the star have stopped the rotation");
+                label_click_to_copy(
+                    ui,
+                    format!("{:.3e}", f32::from(locking.timescale_gigayears)),
+                );
+                ui.label("Gyr").on_hover_text("billion years");
+                ui.end_row();
+
+                ui.label("Rotation").on_hover_text(format!(
+                    "Compared to the age of the star, {:.2} Gyr",
+                    star.age_gigayears
+                ));
+                label_click_to_copy(ui, locking.state.to_string());
+                ui.end_row();
+            });
+    }
+
+    fn show_seasons(&mut self, ui: &mut Ui, star: &MainSequenceStar) {
+        ui.heading("Seasons");
+
+        let seasons =
+            Seasons::calculate_parameters(star, self.orbit_calculator.orbit(), self.axial_tilt());
+
+        egui::Grid::new("season_parameters")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Tropics").on_hover_text(
+                    "Between the tropics, the star is directly overhead at least once a year",
+                );
+                label_click_to_copy(ui, format!("{:.2}", seasons.tropic_latitude));
+                ui.label(Degree::SYMBOL).on_hover_text("Latitude, north and south");
+                ui.end_row();
+
+                ui.label("Polar circles").on_hover_text(
+                    "Beyond the polar circles, there is at least one day a year that the star doesn't set, and one that it doesn't rise",
+                );
+                label_click_to_copy(ui, format!("{:.2}", seasons.polar_circle_latitude));
+                ui.label(Degree::SYMBOL).on_hover_text("Latitude, north and south");
+                ui.end_row();
+
+                ui.label("Time of year")
+                    .on_hover_text("Fraction of the year since the planet was closest to the star");
+                ui.add(egui::Slider::new(&mut self.input_time_of_year, 0.0..=1.0));
+                ui.end_row();
+
+                // On the hemisphere that has summer, the star doesn't set beyond this latitude.
+                // The same latitude on the other hemisphere has polar night.
+                let polar_day = f32::from(seasons.polar_day_latitude(self.input_time_of_year));
+
+                ui.label("Polar day")
+                    .on_hover_text("Beyond this latitude, the star doesn't set");
+                label_click_to_copy(ui, format!("{:.2}", polar_day));
+                ui.label(Degree::SYMBOL)
+                    .on_hover_text("Latitude, positive in the north");
+                ui.end_row();
+
+                ui.label("Polar night")
+                    .on_hover_text("Beyond this latitude, the star doesn't rise");
+                label_click_to_copy(ui, format!("{:.2}", -polar_day));
+                ui.label(Degree::SYMBOL)
+                    .on_hover_text("Latitude, positive in the north");
+                ui.end_row();
+            });
+
+        seasons_heatmap(ui, &seasons, &mut self.insolation_grid);
+    }
+
+    fn show_climate(&mut self, ui: &mut Ui, star: &MainSequenceStar, unit_system: UnitSystem) {
+        ui.heading("Temperature");
+
+        egui::Grid::new("climate_parameters")
+            .num_columns(3)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Albedo")
+                    .on_hover_text("Fraction of the light of the star that is reflected");
+                ui.add(egui::Slider::new(&mut self.input_albedo, 0.0..=1.0));
+                ui.end_row();
+
+                ui.label("Greenhouse effect")
+                    .on_hover_text("Relative to the greenhouse effect of the earth");
+                ui.add(
+                    egui::Slider::new(&mut self.input_greenhouse_effect, 0.0..=100.0)
+                        .logarithmic(true),
+                );
+                ui.end_row();
+
+                self.climate = Climate::calculate_parameters(
+                    star,
+                    self.orbit_calculator.orbit(),
+                    self.input_albedo,
+                    self.input_greenhouse_effect,
+                );
+                let climate = &self.climate;
+
+                ui.label("Equilibrium temperature")
+                    .on_hover_text("Temperature when only heated by the star");
+                unit_value_click_to_copy(ui, climate.equilibrium_temperature, unit_system, 0);
+                ui.end_row();
+
+                ui.label("Surface temperature");
+                unit_value_click_to_copy(ui, climate.surface_temperature, unit_system, 0);
+                ui.end_row();
+
+                ui.label("");
+                label_click_to_copy(
+                    ui,
+                    format!("{:.0}", Celsius::from(climate.surface_temperature)),
+                );
+                ui.label(Celsius::SYMBOL).on_hover_text(Celsius::NAME);
+                ui.end_row();
+
+                ui.label("Verdict");
+                let color = match climate.verdict {
+                    TemperatureVerdict::Habitable => Color32::from_rgb(0, 180, 0),
+                    TemperatureVerdict::TooCold => Color32::from_rgb(80, 140, 255),
+                    TemperatureVerdict::TooHot => Color32::from_rgb(230, 80, 0),
+                };
+                ui.colored_label(color, climate.verdict.to_string());
+                ui.end_row();
+            });
+    }
+}
@@ -0,0 +1,781 @@
+use crate::widgets::{format_unit_value, label_click_to_copy, unit_label};
+use eframe::egui;
+use eframe::egui::{Color32, Ui};
+use worldsmith_lib::moon::{Moon, MoonHost};
+use worldsmith_lib::orbit::Orbit;
+use worldsmith_lib::planet::{PlanetComposition, TerrestrialPlanet};
+use worldsmith_lib::system::{AsteroidBelt, BinaryMember, StarSystem, SystemMoon, SystemStar};
+use worldsmith_lib::tidal::{TidalLocking, TYPICAL_INITIAL_ROTATION_PERIOD_HOURS};
+use worldsmith_lib::unit_system::UnitSystem;
+use worldsmith_lib::units::{
+    AstronomicalUnit, Degree, EarthDay, EarthMass, EarthYear, Gigayear, Hour, JupiterMass,
+    Kilometer, SolarMass, Unit,
+};
+use worldsmith_lib::MainSequenceStar;
+
+/// Shows the whole star system document, and allows editing everything in it.
+/// Stars are taken from the star calculator.
+pub fn show_system_editor(
+    ui: &mut Ui,
+    system: &mut StarSystem,
+    calculator_star: &MainSequenceStar,
+    unit_system: UnitSystem,
+) {
+    ui.heading("Star system");
+
+    egui::Grid::new("star_system")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(&mut system.name);
+            ui.end_row();
+
+            ui.label("Notes");
+            ui.text_edit_multiline(&mut system.notes);
+            ui.end_row();
+        });
+
+    ui.separator();
+    show_stars(ui, system, calculator_star);
+    ui.separator();
+    show_binaries(ui, system, unit_system);
+    ui.separator();
+    show_planets(ui, system);
+    ui.separator();
+    show_giants(ui, system);
+    ui.separator();
+    show_belts(ui, system);
+}
+
+fn show_stars(ui: &mut Ui, system: &mut StarSystem, calculator_star: &MainSequenceStar) {
+    ui.heading("Stars");
+
+    let mut replace_index = None;
+    let mut remove_index = None;
+
+    for index in 0..system.stars().len() {
+        ui.push_id(("star", index), |ui| {
+            if let Some(system_star) = system.star_mut(index) {
+                egui::CollapsingHeader::new(format!(
+                    "{} ({}, {:.3} {})",
+                    system_star.name,
+                    system_star.star.classification(),
+                    system_star.star.mass,
+                    SolarMass::SYMBOL
+                ))
+                .id_source("header")
+                .show(ui, |ui| {
+                    show_name_and_notes(ui, &mut system_star.name, &mut system_star.notes);
+
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button("Replace with calculator star")
+                            .on_hover_text("Replaces this star with the one in the star calculator")
+                            .clicked()
+                        {
+                            replace_index = Some(index);
+                        }
+                        // The primary star can't be removed.
+                        if index != 0 && ui.button("Remove").clicked() {
+                            remove_index = Some(index);
+                        }
+                    });
+                });
+            }
+        });
+    }
+
+    if let Some(index) = replace_index {
+        system.replace_star(index, calculator_star.clone());
+    }
+    if let Some(index) = remove_index {
+        system.remove_star(index);
+    }
+
+    if ui.button("Add calculator star").clicked() {
+        let name = format!("Star {}", system.stars().len() + 1);
+        system.add_star(SystemStar::new(name, calculator_star.clone()));
+    }
+}
+
+fn show_binaries(ui: &mut Ui, system: &mut StarSystem, unit_system: UnitSystem) {
+    ui.heading("Binaries");
+
+    let mut orbits_changed = false;
+    let mut remove_index = None;
+
+    for index in 0..system.binaries().len() {
+        // Members can be any star, or any binary that comes before this one.
+        let mut member_names: Vec<(BinaryMember, String)> = system
+            .stars()
+            .iter()
+            .enumerate()
+            .map(|(star, s)| (BinaryMember::Star(star), s.name.clone()))
+            .collect();
+        member_names.extend(
+            system.binaries()[..index]
+                .iter()
+                .enumerate()
+                .map(|(binary, b)| (BinaryMember::Binary(binary), b.name.clone())),
+        );
+
+        ui.push_id(("binary", index), |ui| {
+            if let Some(binary) = system.binary_mut(index) {
+                egui::CollapsingHeader::new(format!(
+                    "{} ({:.3} {}, {:.3} {})",
+                    binary.name,
+                    binary.orbit.semi_major_axis,
+                    AstronomicalUnit::SYMBOL,
+                    binary.orbit.period,
+                    EarthYear::SYMBOL,
+                ))
+                .id_source("header")
+                .show(ui, |ui| {
+                    show_name_and_notes(ui, &mut binary.name, &mut binary.notes);
+
+                    egui::Grid::new("binary").num_columns(3).show(ui, |ui| {
+                        ui.label("Primary");
+                        orbits_changed |= binary_member_combo_box(
+                            ui,
+                            "primary",
+                            &mut binary.primary,
+                            &member_names,
+                        );
+                        ui.end_row();
+
+                        ui.label("Secondary");
+                        orbits_changed |= binary_member_combo_box(
+                            ui,
+                            "secondary",
+                            &mut binary.secondary,
+                            &member_names,
+                        );
+                        ui.end_row();
+
+                        let orbit = &mut binary.orbit;
+                        let mut semi_major_axis: f32 = orbit.semi_major_axis.into();
+                        ui.label("Separation")
+                            .on_hover_text("Semi-major axis of the orbit of the two members");
+                        if ui
+                            .add(
+                                egui::Slider::new(&mut semi_major_axis, 0.01..=10_000.0)
+                                    .logarithmic(true),
+                            )
+                            .changed()
+                        {
+                            orbit.semi_major_axis = semi_major_axis.into();
+                            orbits_changed = true;
+                        }
+                        ui.label(AstronomicalUnit::SYMBOL)
+                            .on_hover_text(AstronomicalUnit::NAME);
+                        ui.end_row();
+
+                        ui.label("Eccentricity");
+                        orbits_changed |= ui
+                            .add(egui::Slider::new(&mut orbit.eccentricity, 0.0..=0.99))
+                            .changed();
+                        ui.end_row();
+
+                        ui.label("Period");
+                        label_click_to_copy(ui, format!("{:.3}", orbit.period));
+                        ui.label(EarthYear::SYMBOL).on_hover_text(EarthYear::NAME);
+                        ui.end_row();
+
+                        let distance = |value: AstronomicalUnit| {
+                            format_unit_value(value, unit_system, 3)
+                        };
+
+                        ui.label("Distance to barycenter")
+                            .on_hover_text("Average distance of the primary and secondary to the center of mass");
+                        label_click_to_copy(
+                            ui,
+                            format!(
+                                "{} - {}",
+                                distance(orbit.primary_barycenter_distance),
+                                distance(orbit.secondary_barycenter_distance),
+                            ),
+                        );
+                        unit_label::<AstronomicalUnit>(ui, unit_system);
+                        ui.end_row();
+
+                        ui.label("Stable orbits around primary")
+                            .on_hover_text("Planets orbiting only the primary need to stay within this distance");
+                        label_click_to_copy(
+                            ui,
+                            format!("< {}", distance(orbit.primary_stable_orbit_limit)),
+                        );
+                        unit_label::<AstronomicalUnit>(ui, unit_system);
+                        ui.end_row();
+
+                        ui.label("Stable orbits around secondary")
+                            .on_hover_text("Planets orbiting only the secondary need to stay within this distance");
+                        label_click_to_copy(
+                            ui,
+                            format!("< {}", distance(orbit.secondary_stable_orbit_limit)),
+                        );
+                        unit_label::<AstronomicalUnit>(ui, unit_system);
+                        ui.end_row();
+
+                        ui.label("Stable circumbinary orbits")
+                            .on_hover_text("Planets orbiting both members need to stay beyond this distance");
+                        label_click_to_copy(
+                            ui,
+                            format!("> {}", distance(orbit.circumbinary_stable_orbit_limit)),
+                        );
+                        unit_label::<AstronomicalUnit>(ui, unit_system);
+                        ui.end_row();
+
+                        ui.label("Circumbinary habitable zone");
+                        label_click_to_copy(
+                            ui,
+                            format!(
+                                "{} - {}",
+                                distance(orbit.circumbinary_habitable_zone_inner_edge),
+                                distance(orbit.circumbinary_habitable_zone_outer_edge),
+                            ),
+                        );
+                        unit_label::<AstronomicalUnit>(ui, unit_system);
+                        ui.end_row();
+                    });
+
+                    if !binary.orbit.is_circumbinary_habitable_zone_stable() {
+                        ui.colored_label(
+                            Color32::from_rgb(230, 150, 0),
+                            "⚠ Planets in (the inner part of) the circumbinary habitable zone would not have stable orbits.",
+                        );
+                    }
+
+                    if ui.button("Remove binary").clicked() {
+                        remove_index = Some(index);
+                    }
+                });
+            }
+        });
+    }
+
+    if let Some(index) = remove_index {
+        system.remove_binary(index);
+    }
+    if orbits_changed {
+        system.recalculate_orbits();
+    }
+
+    if system.stars().len() >= 2 && ui.button("Add binary").clicked() {
+        let name = format!("Binary {}", system.binaries().len() + 1);
+        system.add_binary(
+            name,
+            BinaryMember::Star(0),
+            BinaryMember::Star(1),
+            20.0.into(),
+            0.3,
+        );
+    }
+}
+
+fn show_planets(ui: &mut Ui, system: &mut StarSystem) {
+    ui.heading("Planets");
+
+    let star_names: Vec<String> = system.stars().iter().map(|s| s.name.clone()).collect();
+    let star_ages: Vec<Gigayear> = system
+        .stars()
+        .iter()
+        .map(|s| s.star.age_gigayears)
+        .collect();
+    let stable_orbit_limits: Vec<Option<AstronomicalUnit>> = (0..system.stars().len())
+        .map(|star| system.stable_orbit_limit(star))
+        .collect();
+    let mut orbits_changed = false;
+    let mut remove_index = None;
+
+    for (index, system_planet) in system.planets.iter_mut().enumerate() {
+        ui.push_id(("planet", index), |ui| {
+            egui::CollapsingHeader::new(format!(
+                "{} ({:.3} {}, {:.3} {})",
+                system_planet.name,
+                system_planet.orbit.semi_major_axis,
+                AstronomicalUnit::SYMBOL,
+                system_planet.orbit.period,
+                EarthYear::SYMBOL,
+            ))
+            .id_source("header")
+            .show(ui, |ui| {
+                show_name_and_notes(ui, &mut system_planet.name, &mut system_planet.notes);
+
+                egui::Grid::new("planet").num_columns(3).show(ui, |ui| {
+                    ui.label("Orbits");
+                    orbits_changed |=
+                        host_star_combo_box(ui, &mut system_planet.host_star, &star_names);
+                    ui.end_row();
+
+                    let planet = &mut system_planet.planet;
+                    let mut mass: f32 = planet.mass.into();
+                    ui.label("Mass");
+                    if ui
+                        .add(egui::Slider::new(&mut mass, 0.01..=10.0).logarithmic(true))
+                        .changed()
+                    {
+                        *planet = TerrestrialPlanet::calculate_parameters(
+                            mass.into(),
+                            planet.composition.clone(),
+                        );
+                        orbits_changed = true;
+                    }
+                    ui.label(EarthMass::SYMBOL).on_hover_text(EarthMass::NAME);
+                    ui.end_row();
+
+                    orbits_changed |= orbit_sliders(ui, &mut system_planet.orbit);
+
+                    let mut axial_tilt: f32 = system_planet.axial_tilt.into();
+                    ui.label("Axial tilt")
+                        .on_hover_text("Angle between the rotation axis and the orbit");
+                    if ui
+                        .add(egui::Slider::new(&mut axial_tilt, 0.0..=180.0))
+                        .changed()
+                    {
+                        system_planet.axial_tilt = axial_tilt.into();
+                    }
+                    ui.label(Degree::SYMBOL).on_hover_text(Degree::NAME);
+                    ui.end_row();
+                });
+
+                if let Some(Some(limit)) = stable_orbit_limits.get(system_planet.host_star) {
+                    show_stability_warning(ui, &system_planet.orbit, *limit);
+                }
+
+                let age = star_ages
+                    .get(system_planet.host_star)
+                    .copied()
+                    .unwrap_or_default();
+                let host = MoonHost::from(&system_planet.planet);
+                orbits_changed |= show_moons(ui, &mut system_planet.moons, &host, age);
+
+                if ui.button("Remove planet").clicked() {
+                    remove_index = Some(index);
+                }
+            });
+        });
+    }
+
+    if let Some(index) = remove_index {
+        system.planets.remove(index);
+    }
+    if orbits_changed {
+        system.recalculate_orbits();
+    }
+
+    ui.label("Planets can be added from the planet calculator.");
+}
+
+/// Shows the sliders of an orbit as rows of a grid.
+/// Returns true if the orbit was changed, after which it should be recalculated.
+fn orbit_sliders(ui: &mut Ui, orbit: &mut Orbit) -> bool {
+    let mut changed = false;
+
+    let mut semi_major_axis: f32 = orbit.semi_major_axis.into();
+    ui.label("Semi-major axis");
+    if ui
+        .add(egui::Slider::new(&mut semi_major_axis, 0.01..=1000.0).logarithmic(true))
+        .changed()
+    {
+        orbit.semi_major_axis = semi_major_axis.into();
+        changed = true;
+    }
+    ui.label(AstronomicalUnit::SYMBOL)
+        .on_hover_text(AstronomicalUnit::NAME);
+    ui.end_row();
+
+    ui.label("Eccentricity");
+    changed |= ui
+        .add(egui::Slider::new(&mut orbit.eccentricity, 0.0..=0.99))
+        .changed();
+    ui.end_row();
+
+    let mut inclination: f32 = orbit.inclination.into();
+    ui.label("Inclination");
+    if ui
+        .add(egui::Slider::new(&mut inclination, 0.0..=180.0))
+        .changed()
+    {
+        orbit.inclination = inclination.into();
+        changed = true;
+    }
+    ui.label(Degree::SYMBOL).on_hover_text(Degree::NAME);
+    ui.end_row();
+
+    changed
+}
+
+/// Warns when the orbit goes beyond the given limit,
+/// set by the other star in a binary.
+fn show_stability_warning(ui: &mut Ui, orbit: &Orbit, limit: AstronomicalUnit) {
+    if orbit.apoapsis > limit {
+        ui.colored_label(
+            Color32::from_rgb(230, 150, 0),
+            format!(
+                "⚠ This orbit is not stable, because of the other star in the binary. Stay within {:.3} {}.",
+                limit,
+                AstronomicalUnit::SYMBOL
+            ),
+        );
+    }
+}
+
+fn show_giants(ui: &mut Ui, system: &mut StarSystem) {
+    ui.heading("Giants");
+
+    let star_names: Vec<String> = system.stars().iter().map(|s| s.name.clone()).collect();
+    let star_ages: Vec<Gigayear> = system
+        .stars()
+        .iter()
+        .map(|s| s.star.age_gigayears)
+        .collect();
+    let stable_orbit_limits: Vec<Option<AstronomicalUnit>> = (0..system.stars().len())
+        .map(|star| system.stable_orbit_limit(star))
+        .collect();
+    let mut orbits_changed = false;
+    let mut remove_index = None;
+
+    for (index, system_giant) in system.giants.iter_mut().enumerate() {
+        ui.push_id(("giant", index), |ui| {
+            egui::CollapsingHeader::new(format!(
+                "{} ({}, {:.3} {}, {:.3} {})",
+                system_giant.name,
+                system_giant.planet.kind,
+                system_giant.orbit.semi_major_axis,
+                AstronomicalUnit::SYMBOL,
+                system_giant.orbit.period,
+                EarthYear::SYMBOL,
+            ))
+            .id_source("header")
+            .show(ui, |ui| {
+                show_name_and_notes(ui, &mut system_giant.name, &mut system_giant.notes);
+
+                egui::Grid::new("giant").num_columns(3).show(ui, |ui| {
+                    ui.label("Orbits");
+                    orbits_changed |=
+                        host_star_combo_box(ui, &mut system_giant.host_star, &star_names);
+                    ui.end_row();
+
+                    // The giant itself is recalculated together with the orbit,
+                    // because its temperature depends on the distance to the star.
+                    let giant = &mut system_giant.planet;
+                    let mut mass: f32 = giant.mass.into();
+                    ui.label("Mass");
+                    if ui
+                        .add(egui::Slider::new(&mut mass, 0.04..=13.0).logarithmic(true))
+                        .changed()
+                    {
+                        giant.mass = mass.into();
+                        orbits_changed = true;
+                    }
+                    ui.label(JupiterMass::SYMBOL)
+                        .on_hover_text(JupiterMass::NAME);
+                    ui.end_row();
+
+                    let mut rotation_period: f32 = giant.rotation_period.into();
+                    ui.label("Rotation period");
+                    if ui
+                        .add(egui::Slider::new(&mut rotation_period, 3.0..=100.0).logarithmic(true))
+                        .changed()
+                    {
+                        giant.rotation_period = rotation_period.into();
+                        orbits_changed = true;
+                    }
+                    ui.label(Hour::SYMBOL).on_hover_text(Hour::NAME);
+                    ui.end_row();
+
+                    ui.label("Class");
+                    label_click_to_copy(
+                        ui,
+                        format!("{} ({})", giant.class, giant.class.description()),
+                    );
+                    ui.end_row();
+
+                    orbits_changed |= orbit_sliders(ui, &mut system_giant.orbit);
+                });
+
+                if let Some(Some(limit)) = stable_orbit_limits.get(system_giant.host_star) {
+                    show_stability_warning(ui, &system_giant.orbit, *limit);
+                }
+
+                let age = star_ages
+                    .get(system_giant.host_star)
+                    .copied()
+                    .unwrap_or_default();
+                let host = MoonHost::from(&system_giant.planet);
+                orbits_changed |= show_moons(ui, &mut system_giant.moons, &host, age);
+
+                if ui.button("Remove giant").clicked() {
+                    remove_index = Some(index);
+                }
+            });
+        });
+    }
+
+    if let Some(index) = remove_index {
+        system.giants.remove(index);
+    }
+    if orbits_changed {
+        system.recalculate_orbits();
+    }
+
+    ui.label("Giants can be added from the giant calculator.");
+}
+
+/// Returns true if a moon was changed or added, after which the orbits should be recalculated.
+/// The moons are assumed to be as old as the star, which is `age_gigayears` old.
+fn show_moons(
+    ui: &mut Ui,
+    moons: &mut Vec<SystemMoon>,
+    host: &MoonHost,
+    age_gigayears: Gigayear,
+) -> bool {
+    ui.label("Moons");
+
+    let mut changed = false;
+    let mut remove_index = None;
+
+    for (index, moon) in moons.iter_mut().enumerate() {
+        ui.push_id(("moon", index), |ui| {
+            egui::CollapsingHeader::new(&moon.name)
+                .id_source("header")
+                .show(ui, |ui| {
+                    show_name_and_notes(ui, &mut moon.name, &mut moon.notes);
+
+                    egui::Grid::new("moon").num_columns(3).show(ui, |ui| {
+                        let mut mass: f32 = moon.body.mass.into();
+                        ui.label("Mass");
+                        if ui
+                            .add(egui::Slider::new(&mut mass, 0.0001..=1.0).logarithmic(true))
+                            .changed()
+                        {
+                            moon.body = TerrestrialPlanet::calculate_parameters(
+                                mass.into(),
+                                moon.body.composition.clone(),
+                            );
+                            changed = true;
+                        }
+                        ui.label(EarthMass::SYMBOL).on_hover_text(EarthMass::NAME);
+                        ui.end_row();
+
+                        let mut distance: f32 = moon.semi_major_axis.into();
+                        ui.label("Distance");
+                        if ui
+                            .add(
+                                egui::Slider::new(&mut distance, 1000.0..=10_000_000.0)
+                                    .logarithmic(true),
+                            )
+                            .changed()
+                        {
+                            moon.semi_major_axis = distance.into();
+                            changed = true;
+                        }
+                        ui.label(Kilometer::SYMBOL).on_hover_text(Kilometer::NAME);
+                        ui.end_row();
+
+                        let derived = &moon.moon;
+                        ui.label("Sidereal month")
+                            .on_hover_text("Time for one orbit, relative to the stars");
+                        label_click_to_copy(ui, format!("{:.3}", derived.sidereal_period));
+                        ui.label(EarthDay::SYMBOL).on_hover_text(EarthDay::NAME);
+                        ui.end_row();
+
+                        ui.label("Synodic month")
+                            .on_hover_text("Time from one full moon to the next");
+                        match derived.synodic_period {
+                            Some(period) => label_click_to_copy(ui, format!("{:.3}", period)),
+                            None => label_click_to_copy(ui, "-".to_string()),
+                        };
+                        ui.label(EarthDay::SYMBOL).on_hover_text(EarthDay::NAME);
+                        ui.end_row();
+
+                        ui.label("Stable distance").on_hover_text(
+                            "Between the roche limit, and the edge of the stable part of the hill sphere",
+                        );
+                        label_click_to_copy(
+                            ui,
+                            format!(
+                                "{:.0} - {:.0}",
+                                derived.roche_limit, derived.stable_orbit_limit
+                            ),
+                        );
+                        ui.label(Kilometer::SYMBOL).on_hover_text(Kilometer::NAME);
+                        ui.end_row();
+
+                        ui.label("Tides")
+                            .on_hover_text("Relative to the tides of our moon on the earth");
+                        label_click_to_copy(ui, format!("{:.3}", derived.tidal_force));
+                        ui.end_row();
+
+                        ui.label("Angular size")
+                            .on_hover_text("How large the moon looks from the surface of the planet");
+                        label_click_to_copy(ui, format!("{:.3}", derived.angular_size));
+                        ui.label(Degree::SYMBOL).on_hover_text(Degree::NAME);
+                        ui.end_row();
+
+                        let locking = TidalLocking::calculate_for_moon(
+                            &moon.body,
+                            TYPICAL_INITIAL_ROTATION_PERIOD_HOURS.into(),
+                            host,
+                            moon.semi_major_axis,
+                            age_gigayears,
+                        );
+                        ui.label("Rotation").on_hover_text(format!(
+                            "Tidally locked to the planet after {:.3e} Gyr",
+                            f32::from(locking.timescale_gigayears)
+                        ));
+                        label_click_to_copy(ui, locking.state.to_string());
+                        ui.end_row();
+                    });
+
+                    if !moon.moon.is_orbit_stable() {
+                        ui.colored_label(
+                            Color32::from_rgb(230, 150, 0),
+                            "⚠ This moon is either torn apart by the planet, or pulled away by the star.",
+                        );
+                    }
+
+                    if ui.button("Remove moon").clicked() {
+                        remove_index = Some(index);
+                    }
+                });
+        });
+    }
+
+    if let Some(index) = remove_index {
+        moons.remove(index);
+    }
+
+    if ui.button("Add moon").clicked() {
+        // Starts out as a copy of our own moon.
+        moons.push(SystemMoon {
+            name: format!("Moon {}", moons.len() + 1),
+            notes: String::new(),
+            body: TerrestrialPlanet::calculate_parameters(
+                0.0123.into(),
+                PlanetComposition::new(0.05, 0.95, 0.),
+            ),
+            semi_major_axis: 384_400.0.into(),
+            moon: Moon::default(),
+        });
+        changed = true;
+    }
+
+    changed
+}
+
+fn show_belts(ui: &mut Ui, system: &mut StarSystem) {
+    ui.heading("Belts");
+
+    let star_names: Vec<String> = system.stars().iter().map(|s| s.name.clone()).collect();
+    let mut remove_index = None;
+
+    for (index, belt) in system.belts.iter_mut().enumerate() {
+        ui.push_id(("belt", index), |ui| {
+            egui::CollapsingHeader::new(&belt.name)
+                .id_source("header")
+                .show(ui, |ui| {
+                    show_name_and_notes(ui, &mut belt.name, &mut belt.notes);
+
+                    egui::Grid::new("belt").num_columns(3).show(ui, |ui| {
+                        ui.label("Orbits");
+                        host_star_combo_box(ui, &mut belt.host_star, &star_names);
+                        ui.end_row();
+
+                        let mut inner_edge: f32 = belt.inner_edge.into();
+                        let mut outer_edge: f32 = belt.outer_edge.into();
+
+                        ui.label("Inner edge");
+                        ui.add(egui::Slider::new(&mut inner_edge, 0.01..=1000.0).logarithmic(true));
+                        ui.label(AstronomicalUnit::SYMBOL)
+                            .on_hover_text(AstronomicalUnit::NAME);
+                        ui.end_row();
+
+                        ui.label("Outer edge");
+                        ui.add(egui::Slider::new(&mut outer_edge, 0.01..=1000.0).logarithmic(true));
+                        ui.label(AstronomicalUnit::SYMBOL)
+                            .on_hover_text(AstronomicalUnit::NAME);
+                        ui.end_row();
+
+                        belt.inner_edge = inner_edge.min(outer_edge).into();
+                        belt.outer_edge = outer_edge.max(inner_edge).into();
+                    });
+
+                    if ui.button("Remove belt").clicked() {
+                        remove_index = Some(index);
+                    }
+                });
+        });
+    }
+
+    if let Some(index) = remove_index {
+        system.belts.remove(index);
+    }
+
+    if ui.button("Add belt").clicked() {
+        // Place it just inside the frost line, like our own asteroid belt.
+        let frost_line: f32 = system.primary_star().star.frost_line.into();
+        system.belts.push(AsteroidBelt {
+            name: format!("Belt {}", system.belts.len() + 1),
+            notes: String::new(),
+            host_star: 0,
+            inner_edge: (frost_line * 0.45).into(),
+            outer_edge: (frost_line * 0.65).into(),
+        });
+    }
+}
+
+fn show_name_and_notes(ui: &mut Ui, name: &mut String, notes: &mut String) {
+    egui::Grid::new("name_and_notes")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name");
+            ui.text_edit_singleline(name);
+            ui.end_row();
+
+            ui.label("Notes");
+            ui.text_edit_multiline(notes);
+            ui.end_row();
+        });
+}
+
+/// Returns true if a different member was selected.
+fn binary_member_combo_box(
+    ui: &mut Ui,
+    id_source: &str,
+    member: &mut BinaryMember,
+    member_names: &[(BinaryMember, String)],
+) -> bool {
+    let previous_member = *member;
+
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(
+            member_names
+                .iter()
+                .find(|(m, _)| m == member)
+                .map(|(_, name)| name.clone())
+                .unwrap_or_default(),
+        )
+        .show_ui(ui, |ui| {
+            for (option, name) in member_names {
+                ui.selectable_value(member, *option, name);
+            }
+        });
+
+    *member != previous_member
+}
+
+/// Returns true if a different star was selected.
+fn host_star_combo_box(ui: &mut Ui, host_star: &mut usize, star_names: &[String]) -> bool {
+    let previous_host_star = *host_star;
+
+    egui::ComboBox::from_id_source("host_star")
+        .selected_text(star_names.get(*host_star).cloned().unwrap_or_default())
+        .show_ui(ui, |ui| {
+            for (index, name) in star_names.iter().enumerate() {
+                ui.selectable_value(host_star, index, name);
+            }
+        });
+
+    *host_star != previous_host_star
+}
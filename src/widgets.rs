@@ -1,8 +1,16 @@
 use eframe::egui::{
-    Align2, Button, Color32, CursorIcon, FontFamily, FontId, Painter, Pos2, Sense, Ui, Vec2,
-    Widget, WidgetText,
+    Align2, Button, Color32, CursorIcon, FontFamily, FontId, Pos2, Rect, Sense, Shape, Stroke, Ui,
+    Vec2, Widget, WidgetText,
 };
-use worldsmith_lib::units::{SolarRadius, Unit};
+use std::ops::RangeInclusive;
+use worldsmith_lib::color::calculate_display_color;
+use worldsmith_lib::seasons::Seasons;
+use worldsmith_lib::unit_system::{ConvertUnit, UnitSystem};
+use worldsmith_lib::units::{
+    calculate_orbital_period, AstronomicalUnit, ColorRgb, Degree, EarthInsolation, EarthYear,
+    Kelvin, SolarLuminosity, SolarRadius, SpectralClass, Unit,
+};
+use worldsmith_lib::{MainSequenceStar, MAIN_SEQUENCE_MIN_MASS_SOLAR, STAR_MAX_MASS_SOLAR};
 
 pub fn label_click_to_copy(ui: &mut Ui, text: impl Into<WidgetText>) {
     let text = text.into();
@@ -17,6 +25,41 @@ pub fn label_click_to_copy(ui: &mut Ui, text: impl Into<WidgetText>) {
     }
 }
 
+/// The value in the unit system chosen by the user, followed by its unit.
+/// Takes up two columns of a grid.
+pub fn unit_value_click_to_copy<U: ConvertUnit>(
+    ui: &mut Ui,
+    value: U,
+    unit_system: UnitSystem,
+    decimals: usize,
+) {
+    label_click_to_copy(ui, format_unit_value(value, unit_system, decimals));
+    unit_label::<U>(ui, unit_system);
+}
+
+/// The value in the unit system chosen by the user, without its unit.
+/// Very large and very small values are shown in scientific notation.
+pub fn format_unit_value<U: ConvertUnit>(
+    value: U,
+    unit_system: UnitSystem,
+    decimals: usize,
+) -> String {
+    let value = value.convert(unit_system);
+    let magnitude = value.abs();
+
+    if magnitude >= 1e7 || (magnitude > 0. && magnitude < 0.1f64.powi(decimals as i32)) {
+        format!("{:.4e}", value)
+    } else {
+        format!("{:.*}", decimals, value)
+    }
+}
+
+/// The symbol of the unit in the unit system chosen by the user, with its name on hover.
+pub fn unit_label<U: ConvertUnit>(ui: &mut Ui, unit_system: UnitSystem) {
+    let conversion = U::conversion(unit_system);
+    ui.label(conversion.symbol).on_hover_text(conversion.name);
+}
+
 pub fn color_click_to_copy(ui: &mut Ui, color: impl Into<Color32>) {
     let color = color.into();
 
@@ -31,6 +74,10 @@ pub fn color_click_to_copy(ui: &mut Ui, color: impl Into<Color32>) {
     }
 }
 
+pub fn to_color32(color: &ColorRgb) -> Color32 {
+    Color32::from_rgb(color.r(), color.g(), color.b())
+}
+
 /// Draws a chart comparing the given radius to other well-known stars.
 pub fn star_size_comparison_chart(ui: &mut Ui, radius: SolarRadius) {
     let widget_size = Vec2::new(400.0, 100.0);
@@ -149,6 +196,130 @@ pub fn star_size_comparison_chart(ui: &mut Ui, radius: SolarRadius) {
     }
 }
 
+/// Draws the habitable zone, frost line and planetary system limits of the given star,
+/// compared to the orbits of the planets in our own solar system,
+/// and how long their years would be around the given star.
+/// The distances are drawn on a logarithmic scale.
+pub fn habitable_zone_chart(ui: &mut Ui, star: &MainSequenceStar) {
+    let widget_size = Vec2::new(400.0, 100.0);
+
+    let (response, painter) = ui.allocate_painter(widget_size, Sense::hover());
+    let rect = response.rect;
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.style().interact(&response);
+
+        // This is drawn directly on the ui painter, instead of the
+        // painter returned by `allocate_painter`, so that the border is not clipped.
+        ui.painter().rect(
+            rect.expand(visuals.expansion),
+            visuals.rounding,
+            visuals.bg_fill,
+            visuals.bg_stroke,
+        );
+
+        painter.text(
+            rect.left_top(),
+            Align2::LEFT_TOP,
+            "habitable zone",
+            FontId::new(15., FontFamily::Proportional),
+            visuals.text_color(),
+        );
+
+        let solar_system_orbits: Vec<(AstronomicalUnit, &'static str)> = vec![
+            (0.39.into(), "Mercury"),
+            (0.72.into(), "Venus"),
+            (1.0.into(), "Earth"),
+            (1.52.into(), "Mars"),
+            (5.2.into(), "Jupiter"),
+            (9.58.into(), "Saturn"),
+            (19.2.into(), "Uranus"),
+            (30.05.into(), "Neptune"),
+        ];
+
+        // Always show the whole solar system, as well as the whole system of the star.
+        let min_distance: f32 = star.inner_system_limit.into();
+        let max_distance: f32 = star.outer_system_limit.into();
+        let distance_range = (min_distance.min(0.39) * 0.5)..=(max_distance.max(30.05) * 1.5);
+
+        let habitable_zone_rect = Rect::from_x_y_ranges(
+            calculate_log_x(star.habitable_zone_inner_edge, &distance_range, rect)
+                ..=calculate_log_x(star.habitable_zone_outer_edge, &distance_range, rect),
+            rect.y_range(),
+        );
+        painter.rect_filled(
+            habitable_zone_rect,
+            0.0,
+            Color32::from_rgb(40, 140, 40).linear_multiply(0.4),
+        );
+
+        let frost_line_x = calculate_log_x(star.frost_line, &distance_range, rect);
+        painter.vline(
+            frost_line_x,
+            rect.y_range(),
+            Stroke::new(2.0, Color32::from_rgb(150, 200, 255)),
+        );
+        painter.text(
+            Pos2::new(frost_line_x, rect.bottom()),
+            Align2::LEFT_BOTTOM,
+            "frost line",
+            FontId::new(12., FontFamily::Proportional),
+            visuals.text_color(),
+        );
+
+        // Planets can not form outside of the system limits, so those parts are shaded.
+        let inner_limit_x = calculate_log_x(star.inner_system_limit, &distance_range, rect);
+        let outer_limit_x = calculate_log_x(star.outer_system_limit, &distance_range, rect);
+        let outside_limits_color = Color32::from_black_alpha(100);
+        painter.rect_filled(
+            Rect::from_x_y_ranges(rect.left()..=inner_limit_x, rect.y_range()),
+            0.0,
+            outside_limits_color,
+        );
+        painter.rect_filled(
+            Rect::from_x_y_ranges(outer_limit_x..=rect.right(), rect.y_range()),
+            0.0,
+            outside_limits_color,
+        );
+
+        // Draw the solar system planets along the center line, alternating the labels
+        // above and below the line, so that they don't overlap each other.
+        painter.hline(rect.x_range(), rect.center().y, visuals.fg_stroke);
+
+        for (i, (distance, name)) in solar_system_orbits.into_iter().enumerate() {
+            let position = Pos2::new(
+                calculate_log_x(distance, &distance_range, rect),
+                rect.center().y,
+            );
+
+            painter.circle_filled(position, 3.0, visuals.fg_stroke.color);
+
+            let (offset, align) = if i % 2 == 0 {
+                (Vec2::new(0., -5.), Align2::CENTER_BOTTOM)
+            } else {
+                (Vec2::new(0., 5.), Align2::CENTER_TOP)
+            };
+            let year_length = calculate_orbital_period(distance, star.current_mass);
+            painter.text(
+                position + offset,
+                align,
+                format!("{}\n{:.2} {}", name, year_length, EarthYear::SYMBOL),
+                FontId::new(12., FontFamily::Proportional),
+                visuals.text_color(),
+            );
+        }
+    }
+}
+
+/// Maps the given distance onto the horizontal axis of the given rect, on a logarithmic scale.
+fn calculate_log_x(
+    distance: AstronomicalUnit,
+    distance_range: &RangeInclusive<f32>,
+    rect: Rect,
+) -> f32 {
+    log_map(distance.into(), distance_range, rect.x_range())
+}
+
 /// Calculates the radius in pixels of a given orbit, relative to the reference radius.
 fn calculate_draw_radius(
     radius: SolarRadius,
@@ -160,3 +331,385 @@ fn calculate_draw_radius(
 
     (radius / reference_radius) * reference_radius_pixels
 }
+
+const INSOLATION_GRID_COLUMNS: usize = 60;
+const INSOLATION_GRID_ROWS: usize = 36;
+
+/// The insolation drawn by [seasons_heatmap], by latitude (rows, north first) and
+/// time of year (columns). Only calculated again when the seasons change.
+pub struct InsolationGrid {
+    seasons: Option<Seasons>,
+    values: Vec<Vec<f32>>,
+    max: f32,
+}
+
+impl InsolationGrid {
+    pub fn new() -> Self {
+        InsolationGrid {
+            seasons: None,
+            values: vec![],
+            max: f32::EPSILON,
+        }
+    }
+
+    fn update(&mut self, seasons: &Seasons) {
+        if self.seasons.as_ref() == Some(seasons) {
+            return;
+        }
+
+        self.values = (0..INSOLATION_GRID_ROWS)
+            .map(|row| {
+                let latitude = 90. - 180. * (row as f32 + 0.5) / INSOLATION_GRID_ROWS as f32;
+                (0..INSOLATION_GRID_COLUMNS)
+                    .map(|column| {
+                        let year_fraction = (column as f32 + 0.5) / INSOLATION_GRID_COLUMNS as f32;
+                        seasons.insolation(latitude.into(), year_fraction).into()
+                    })
+                    .collect()
+            })
+            .collect();
+        self.max = self
+            .values
+            .iter()
+            .flatten()
+            .fold(f32::EPSILON, |max, &value| max.max(value));
+        self.seasons = Some(seasons.clone());
+    }
+}
+
+/// Draws the insolation on a planet by latitude (vertical, north at the top)
+/// and time of year (horizontal, starting at periapsis),
+/// with lines at the equator, tropics and polar circles.
+/// Hovering shows the values at that point.
+pub fn seasons_heatmap(ui: &mut Ui, seasons: &Seasons, grid: &mut InsolationGrid) {
+    let widget_size = Vec2::new(400.0, 200.0);
+    let columns = INSOLATION_GRID_COLUMNS;
+    let rows = INSOLATION_GRID_ROWS;
+
+    let (response, painter) = ui.allocate_painter(widget_size, Sense::hover());
+    let rect = response.rect;
+
+    let year_fraction_at = |x: f32| ((x - rect.left()) / rect.width()).clamp(0.0, 1.0);
+    let latitude_at = |y: f32| 90. - 180. * ((y - rect.top()) / rect.height()).clamp(0.0, 1.0);
+    let latitude_y = |latitude: f32| rect.top() + (90. - latitude) / 180. * rect.height();
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.style().interact(&response);
+
+        let cell_size = Vec2::new(rect.width() / columns as f32, rect.height() / rows as f32);
+        grid.update(seasons);
+        let max_insolation = grid.max;
+
+        for (row, values) in grid.values.iter().enumerate() {
+            for (column, value) in values.iter().enumerate() {
+                let min = rect.left_top()
+                    + Vec2::new(column as f32 * cell_size.x, row as f32 * cell_size.y);
+                painter.rect_filled(
+                    // Slightly larger than the cell, so that there are no gaps between them.
+                    Rect::from_min_size(min, cell_size + Vec2::splat(0.5)),
+                    0.0,
+                    insolation_color(value / max_insolation),
+                );
+            }
+        }
+
+        let tropic: f32 = seasons.tropic_latitude.into();
+        let polar_circle: f32 = seasons.polar_circle_latitude.into();
+        for (latitude, name) in [
+            (polar_circle, "polar circle"),
+            (tropic, "tropic"),
+            (0., "equator"),
+            (-tropic, "tropic"),
+            (-polar_circle, "polar circle"),
+        ] {
+            let y = latitude_y(latitude);
+            painter.hline(
+                rect.x_range(),
+                y,
+                Stroke::new(1.0, Color32::from_white_alpha(120)),
+            );
+            painter.text(
+                Pos2::new(rect.right(), y),
+                Align2::RIGHT_BOTTOM,
+                name,
+                FontId::new(12., FontFamily::Proportional),
+                Color32::WHITE,
+            );
+        }
+
+        painter.text(
+            rect.left_top(),
+            Align2::LEFT_TOP,
+            "insolation over the year",
+            FontId::new(15., FontFamily::Proportional),
+            Color32::WHITE,
+        );
+
+        ui.painter().rect_stroke(
+            rect.expand(visuals.expansion),
+            visuals.rounding,
+            visuals.bg_stroke,
+        );
+    }
+
+    if let Some(position) = response.hover_pos() {
+        let latitude = latitude_at(position.y);
+        let year_fraction = year_fraction_at(position.x);
+        response.on_hover_text_at_pointer(format!(
+            "Latitude {:.0}{}\n{:.0}% of the year after periapsis\nInsolation {:.3} {}\nDaylight {:.0}% of the day",
+            latitude,
+            Degree::SYMBOL,
+            year_fraction * 100.,
+            seasons.insolation(latitude.into(), year_fraction),
+            EarthInsolation::SYMBOL,
+            seasons.daylight_fraction(latitude.into(), year_fraction) * 100.,
+        ));
+    }
+}
+
+/// From dark blue for no light at all, through red, to pale yellow for the most light.
+fn insolation_color(fraction: f32) -> Color32 {
+    let stops = [(10., 10., 40.), (200., 60., 30.), (255., 240., 150.)];
+    let position = fraction.clamp(0.0, 1.0) * (stops.len() - 1) as f32;
+    let index = (position.floor() as usize).min(stops.len() - 2);
+    let t = position - index as f32;
+
+    let (from, to) = (stops[index], stops[index + 1]);
+    let lerp = |a: f32, b: f32| (a + (b - a) * t) as u8;
+    Color32::from_rgb(lerp(from.0, to.0), lerp(from.1, to.1), lerp(from.2, to.2))
+}
+
+/// Well-known stars, as (name, mass, age in Gyr).
+/// They are calculated with the same model as the star calculator, so that they can be loaded into it.
+const HR_DIAGRAM_REFERENCE_STARS: [(&str, f32, f32); 8] = [
+    ("Sun", 1.0, 4.6),
+    ("Proxima Centauri", 0.122, 4.85),
+    ("Alpha Centauri A", 1.1, 5.3),
+    ("Sirius A", 2.063, 0.24),
+    ("Vega", 2.135, 0.455),
+    ("Spica", 11.43, 0.0125),
+    ("Betelgeuse", 16.5, 0.0101),
+    ("Luhman 16 A", 0.0325, 0.8),
+];
+
+/// The stars in the background of the Hertzsprung-Russell diagram, which never change.
+/// Calculated once when the app starts.
+pub struct HrDiagramStars {
+    reference: Vec<(&'static str, MainSequenceStar)>,
+    /// Pairs of newly formed stars and stars that are about to leave the main sequence,
+    /// from light to heavy.
+    main_sequence_band: Vec<(MainSequenceStar, MainSequenceStar)>,
+}
+
+impl HrDiagramStars {
+    pub fn new() -> Self {
+        HrDiagramStars {
+            reference: HR_DIAGRAM_REFERENCE_STARS
+                .iter()
+                .map(|&(name, mass, age)| {
+                    (
+                        name,
+                        MainSequenceStar::calculate_parameters(mass.into(), age.into()),
+                    )
+                })
+                .collect(),
+            main_sequence_band: (0..=40)
+                .map(|step| {
+                    let log_mass = MAIN_SEQUENCE_MIN_MASS_SOLAR.log10()
+                        + step as f32 / 40.
+                            * (STAR_MAX_MASS_SOLAR / MAIN_SEQUENCE_MIN_MASS_SOLAR).log10();
+                    let mass = 10f32.powf(log_mass);
+                    let young = MainSequenceStar::calculate_parameters(mass.into(), 0.0.into());
+                    let old = MainSequenceStar::calculate_parameters(
+                        mass.into(),
+                        young.max_age_gigayears * 0.999,
+                    );
+                    (young, old)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Draws a Hertzsprung-Russell diagram: temperature (hot on the left) against luminosity,
+/// both on a logarithmic scale. Shows the band in which stars spend their main sequence,
+/// the spectral classes along the bottom, reference stars, the saved stars, and the given star.
+///
+/// Returns the star that was clicked, if any, so that it can be loaded into the calculator.
+pub fn hr_diagram(
+    ui: &mut Ui,
+    star: &MainSequenceStar,
+    saved_stars: &[MainSequenceStar],
+    background_stars: &HrDiagramStars,
+    unit_system: UnitSystem,
+) -> Option<MainSequenceStar> {
+    let widget_size = Vec2::new(400.0, 300.0);
+    let temperature_range = 100_000.0f32..=500.0;
+    // Reversed, so that the brightest stars are at the top.
+    let luminosity_range = 1e6f32..=1e-6;
+
+    let (response, painter) = ui.allocate_painter(widget_size, Sense::click());
+    let rect = response.rect;
+    let strip_height = 16.0;
+    let plot_rect = Rect::from_min_max(
+        rect.left_top(),
+        Pos2::new(rect.right(), rect.bottom() - strip_height),
+    );
+
+    let position_of = |star: &MainSequenceStar| {
+        Pos2::new(
+            log_map(
+                star.temperature.into(),
+                &temperature_range,
+                plot_rect.x_range(),
+            ),
+            log_map(
+                star.luminosity.into(),
+                &luminosity_range,
+                plot_rect.y_range(),
+            ),
+        )
+    };
+
+    let reference_stars = &background_stars.reference;
+    let saved_stars: Vec<(String, &MainSequenceStar)> = saved_stars
+        .iter()
+        .enumerate()
+        .map(|(index, star)| {
+            (
+                format!("Saved star {}: {}", index + 1, star.classification()),
+                star,
+            )
+        })
+        .collect();
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.style().interact(&response);
+
+        // This is drawn directly on the ui painter, instead of the
+        // painter returned by `allocate_painter`, so that the border is not clipped.
+        ui.painter().rect(
+            rect.expand(visuals.expansion),
+            visuals.rounding,
+            visuals.bg_fill,
+            visuals.bg_stroke,
+        );
+
+        // The main sequence runs from newly formed stars to stars that are about to leave it.
+        let band_color = visuals.fg_stroke.color.linear_multiply(0.15);
+        let band_edges: Vec<(Pos2, Pos2)> = background_stars
+            .main_sequence_band
+            .iter()
+            .map(|(young, old)| (position_of(young), position_of(old)))
+            .collect();
+        for edges in band_edges.windows(2) {
+            let ((young_a, old_a), (young_b, old_b)) = (edges[0], edges[1]);
+            painter.add(Shape::convex_polygon(
+                vec![young_a, young_b, old_b, old_a],
+                band_color,
+                Stroke::none(),
+            ));
+        }
+
+        // Spectral classes along the bottom, colored like a star of that temperature.
+        let class_boundaries = [
+            100_000., 30_000., 10_000., 7500., 6000., 5200., 3700., 2400., 1300., 500.,
+        ];
+        for boundary in class_boundaries.windows(2) {
+            let (hot, cool): (f32, f32) = (boundary[0], boundary[1]);
+            let middle = Kelvin::from((hot * cool).sqrt());
+            let strip = Rect::from_x_y_ranges(
+                log_map(hot, &temperature_range, plot_rect.x_range())
+                    ..=log_map(cool, &temperature_range, plot_rect.x_range()),
+                plot_rect.bottom()..=rect.bottom(),
+            );
+            painter.rect_filled(strip, 0.0, to_color32(&calculate_display_color(middle)));
+            painter.text(
+                strip.center(),
+                Align2::CENTER_CENTER,
+                SpectralClass::calculate(middle).to_string(),
+                FontId::new(12., FontFamily::Proportional),
+                Color32::BLACK,
+            );
+        }
+
+        for (name, reference) in reference_stars {
+            let position = position_of(reference);
+            painter.circle_filled(position, 3.0, visuals.fg_stroke.color);
+            painter.text(
+                position + Vec2::new(5., 0.),
+                Align2::LEFT_CENTER,
+                name,
+                FontId::new(11., FontFamily::Proportional),
+                visuals.text_color(),
+            );
+        }
+
+        for (_, saved) in &saved_stars {
+            painter.circle(
+                position_of(saved),
+                4.0,
+                to_color32(&saved.color),
+                visuals.fg_stroke,
+            );
+        }
+
+        painter.circle(
+            position_of(star),
+            6.0,
+            to_color32(&star.color),
+            Stroke::new(2.0, visuals.text_color()),
+        );
+
+        painter.text(
+            rect.left_top(),
+            Align2::LEFT_TOP,
+            "Hertzsprung-Russell diagram",
+            FontId::new(15., FontFamily::Proportional),
+            visuals.text_color(),
+        );
+    }
+
+    // Stars within this many pixels of the mouse can be clicked.
+    let pick_distance = 8.0;
+    let hovered = response.hover_pos().and_then(|mouse| {
+        reference_stars
+            .iter()
+            .map(|(name, star)| (name.to_string(), star))
+            .chain(saved_stars.iter().map(|(name, star)| (name.clone(), *star)))
+            .map(|(name, star)| (position_of(star).distance(mouse), name, star))
+            .filter(|(distance, _, _)| *distance <= pick_distance)
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, name, star)| (name, star.clone()))
+    });
+
+    match hovered {
+        Some((name, hovered_star)) => {
+            let response = response.on_hover_text_at_pointer(format!(
+                "{}\n{}, {} {}, {:.3e} {}\nClick to load into the calculator",
+                name,
+                hovered_star.classification(),
+                format_unit_value(hovered_star.temperature, unit_system, 0),
+                Kelvin::conversion(unit_system).symbol,
+                hovered_star.luminosity.convert(unit_system),
+                SolarLuminosity::conversion(unit_system).symbol,
+            ));
+            if response.clicked() {
+                Some(hovered_star)
+            } else {
+                None
+            }
+        }
+        None => None,
+    }
+}
+
+/// Maps the value onto the given range of pixels, on a logarithmic scale.
+/// The value range can be reversed, to let the values go down along the pixels.
+fn log_map(value: f32, value_range: &RangeInclusive<f32>, pixels: RangeInclusive<f32>) -> f32 {
+    let min = value_range.start().log10();
+    let max = value_range.end().log10();
+
+    let fraction = (value.log10() - min) / (max - min);
+    pixels.start() + fraction.clamp(0.0, 1.0) * (pixels.end() - pixels.start())
+}
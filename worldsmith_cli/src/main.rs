@@ -0,0 +1,182 @@
+//! Runs the calculators without a display, so that they can be used in scripts.
+
+use clap::{CommandFactory, ErrorKind, Parser, Subcommand, ValueEnum};
+use worldsmith_lib::units::*;
+use worldsmith_lib::{MainSequenceStar, StarError};
+
+#[derive(Parser)]
+#[clap(name = "worldsmith-cli", version, about)]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+    /// How to print the results.
+    #[clap(long, value_enum, default_value_t = Format::Table, global = true)]
+    format: Format,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Calculate the parameters of a star.
+    Star {
+        /// Mass of the star when it formed, in solar masses.
+        #[clap(long)]
+        mass: f32,
+        /// Age of the star, in giga years (1.000.000.000 years).
+        #[clap(long, default_value_t = 4.6)]
+        age: f32,
+    },
+}
+
+#[derive(Copy, Clone, ValueEnum)]
+enum Format {
+    /// Aligned columns, for reading.
+    Table,
+    /// All fields of the calculation, with the values in their base units.
+    Json,
+    /// Comma separated values, to open in a spreadsheet.
+    Csv,
+}
+
+fn main() {
+    match run(Cli::parse()) {
+        Ok(output) => print!("{}", output),
+        Err(error) => Cli::command()
+            .error(ErrorKind::ValueValidation, error)
+            .exit(),
+    }
+}
+
+/// The text to print for the given command, or why it can't be calculated.
+fn run(cli: Cli) -> Result<String, StarError> {
+    match cli.command {
+        Command::Star { mass, age } => {
+            let star = MainSequenceStar::try_calculate_parameters(mass.into(), age.into())?;
+
+            Ok(match cli.format {
+                Format::Table => format_table(&star_rows(&star)),
+                Format::Csv => format_csv(&star_rows(&star)),
+                Format::Json => {
+                    serde_json::to_string_pretty(&star).expect("a star can always be serialized")
+                        + "\n"
+                }
+            })
+        }
+    }
+}
+
+/// One calculated value, as it is printed.
+struct Row {
+    name: &'static str,
+    value: String,
+    unit: &'static str,
+}
+
+impl Row {
+    fn new(name: &'static str, value: impl ToString, unit: &'static str) -> Self {
+        Row {
+            name,
+            value: value.to_string(),
+            unit,
+        }
+    }
+}
+
+fn star_rows(star: &MainSequenceStar) -> Vec<Row> {
+    let color = |color: &ColorRgb| format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b());
+
+    vec![
+        Row::new("Stellar class", star.classification(), ""),
+        Row::new("Stage", star.stage, ""),
+        Row::new("Mass", star.mass, SolarMass::SYMBOL),
+        Row::new("Current mass", star.current_mass, SolarMass::SYMBOL),
+        Row::new("Age", star.age_gigayears, Gigayear::SYMBOL),
+        Row::new("Maximum age", star.max_age_gigayears, Gigayear::SYMBOL),
+        Row::new("Lifetime elapsed", star.lifetime_fraction * 100., "%"),
+        Row::new("Radius", star.radius, SolarRadius::SYMBOL),
+        Row::new("Luminosity", star.luminosity, SolarLuminosity::SYMBOL),
+        Row::new("Density", star.density, SolarDensity::SYMBOL),
+        Row::new("Temperature", star.temperature, Kelvin::SYMBOL),
+        Row::new("Color", color(&star.color), ""),
+        Row::new("Perceived color", color(&star.perceived_color), ""),
+        Row::new(
+            "Habitable zone inner edge",
+            star.habitable_zone_inner_edge,
+            AstronomicalUnit::SYMBOL,
+        ),
+        Row::new(
+            "Habitable zone outer edge",
+            star.habitable_zone_outer_edge,
+            AstronomicalUnit::SYMBOL,
+        ),
+        Row::new("Frost line", star.frost_line, AstronomicalUnit::SYMBOL),
+        Row::new(
+            "Inner system limit",
+            star.inner_system_limit,
+            AstronomicalUnit::SYMBOL,
+        ),
+        Row::new(
+            "Outer system limit",
+            star.outer_system_limit,
+            AstronomicalUnit::SYMBOL,
+        ),
+    ]
+}
+
+fn format_table(rows: &[Row]) -> String {
+    let name_width = rows.iter().map(|row| row.name.chars().count()).max();
+    let value_width = rows.iter().map(|row| row.value.chars().count()).max();
+
+    rows.iter()
+        .map(|row| {
+            format!(
+                "{:name_width$}  {:>value_width$} {}",
+                row.name,
+                row.value,
+                row.unit,
+                name_width = name_width.unwrap_or(0),
+                value_width = value_width.unwrap_or(0),
+            )
+            .trim_end()
+            .to_string()
+                + "\n"
+        })
+        .collect()
+}
+
+fn format_csv(rows: &[Row]) -> String {
+    let mut csv = String::from("Parameter,Value,Unit\n");
+    for row in rows {
+        csv.push_str(&format!("{},{},{}\n", row.name, row.value, row.unit));
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_sun_csv() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let csv = format_csv(&star_rows(&sun));
+        let mut lines = csv.lines();
+
+        assert_eq!(Some("Parameter,Value,Unit"), lines.next());
+        assert_eq!(Some("Stellar class,G2.8V,"), lines.next());
+    }
+
+    #[test]
+    fn test_star_out_of_range() {
+        let cli = Cli::try_parse_from(["worldsmith-cli", "star", "--mass", "1000"]).unwrap();
+
+        assert_eq!(
+            Some(StarError::MassAboveEddingtonLimit(1000.)),
+            run(cli).err()
+        );
+
+        let cli =
+            Cli::try_parse_from(["worldsmith-cli", "star", "--mass", "1", "--age", "20"]).unwrap();
+        assert_eq!(Some(StarError::AgeBeyondUniverse(20.)), run(cli).err());
+    }
+}
@@ -0,0 +1,333 @@
+//! Which gases a planet can hold on to.
+
+use crate::units::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Temperature of the exosphere of the earth, the outer layer of the atmosphere from which
+/// gases escape. Much hotter than the surface, because it absorbs ultraviolet light of the sun.
+const EARTH_EXOSPHERE_TEMPERATURE_K: f32 = 1273.;
+
+/// A gas is kept for billions of years if the escape velocity is at least this many times
+/// the average speed of its molecules. Below that, the fastest molecules slowly leak away.
+const RETENTION_VELOCITY_FACTOR: f32 = 6.;
+
+/// In J/(mol K).
+const GAS_CONSTANT: f32 = 8.314;
+/// In m/s².
+const EARTH_GRAVITY: f32 = 9.80665;
+
+/// Boiling point of water at 1 atmosphere (1.01325 bar).
+const WATER_BOILING_POINT_K: f32 = 373.15;
+const WATER_BOILING_POINT_PRESSURE_BAR: f32 = 1.01325;
+/// Energy needed to boil water, in J/mol.
+const WATER_HEAT_OF_VAPORIZATION: f32 = 40_660.;
+/// Below this pressure, water can't be liquid. Ice turns directly into vapor.
+const WATER_TRIPLE_POINT_PRESSURE_BAR: f32 = 0.006_117;
+
+/// Humans get altitude sickness below this partial pressure of oxygen.
+const MIN_OXYGEN_PRESSURE_BAR: f32 = 0.16;
+/// Breathing more oxygen than this for days damages the lungs.
+const MAX_OXYGEN_PRESSURE_BAR: f32 = 0.5;
+/// Above this, carbon dioxide causes headaches and drowsiness.
+const MAX_CARBON_DIOXIDE_PRESSURE_BAR: f32 = 0.005;
+/// Above this, nitrogen makes people feel drunk, like divers at 30 meters depth.
+const MAX_NITROGEN_PRESSURE_BAR: f32 = 3.2;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Gas {
+    Hydrogen,
+    Helium,
+    Water,
+    Nitrogen,
+    Oxygen,
+    CarbonDioxide,
+    Methane,
+    Ammonia,
+    Argon,
+}
+
+impl Gas {
+    pub const ALL: [Gas; 9] = [
+        Gas::Hydrogen,
+        Gas::Helium,
+        Gas::Methane,
+        Gas::Ammonia,
+        Gas::Water,
+        Gas::Nitrogen,
+        Gas::Oxygen,
+        Gas::Argon,
+        Gas::CarbonDioxide,
+    ];
+
+    /// In g/mol.
+    pub fn molar_mass(&self) -> f32 {
+        match self {
+            Gas::Hydrogen => 2.016,
+            Gas::Helium => 4.003,
+            Gas::Water => 18.015,
+            Gas::Nitrogen => 28.014,
+            Gas::Oxygen => 31.998,
+            Gas::CarbonDioxide => 44.009,
+            Gas::Methane => 16.043,
+            Gas::Ammonia => 17.031,
+            Gas::Argon => 39.948,
+        }
+    }
+
+    pub fn formula(&self) -> &'static str {
+        match self {
+            Gas::Hydrogen => "H₂",
+            Gas::Helium => "He",
+            Gas::Water => "H₂O",
+            Gas::Nitrogen => "N₂",
+            Gas::Oxygen => "O₂",
+            Gas::CarbonDioxide => "CO₂",
+            Gas::Methane => "CH₄",
+            Gas::Ammonia => "NH₃",
+            Gas::Argon => "Ar",
+        }
+    }
+}
+
+impl Display for Gas {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Gas::Hydrogen => write!(f, "hydrogen"),
+            Gas::Helium => write!(f, "helium"),
+            Gas::Water => write!(f, "water vapor"),
+            Gas::Nitrogen => write!(f, "nitrogen"),
+            Gas::Oxygen => write!(f, "oxygen"),
+            Gas::CarbonDioxide => write!(f, "carbon dioxide"),
+            Gas::Methane => write!(f, "methane"),
+            Gas::Ammonia => write!(f, "ammonia"),
+            Gas::Argon => write!(f, "argon"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct GasRetention {
+    pub gas: Gas,
+    /// Root mean square speed of the molecules in the exosphere.
+    pub thermal_velocity: KilometerPerSecond,
+    /// Whether the planet keeps this gas over geological time.
+    pub retained: bool,
+}
+
+/// Uses the Jeans escape criterion for every gas in [Gas::ALL], from light to heavy.
+pub fn calculate_gas_retention(
+    escape_velocity: KilometerPerSecond,
+    exospheric_temperature: Kelvin,
+) -> Vec<GasRetention> {
+    Gas::ALL
+        .iter()
+        .map(|&gas| {
+            let thermal_velocity = calculate_thermal_velocity(gas, exospheric_temperature);
+            GasRetention {
+                gas,
+                thermal_velocity,
+                retained: f32::from(escape_velocity)
+                    >= RETENTION_VELOCITY_FACTOR * f32::from(thermal_velocity),
+            }
+        })
+        .collect()
+}
+
+/// Root mean square speed of the molecules of a gas at the given temperature.
+pub fn calculate_thermal_velocity(gas: Gas, temperature: Kelvin) -> KilometerPerSecond {
+    let molar_mass_kg = gas.molar_mass() / 1000.;
+    KilometerPerSecond::from(
+        (3. * GAS_CONSTANT * f32::from(temperature) / molar_mass_kg).sqrt() / 1000.,
+    )
+}
+
+/// The exosphere is heated by the star, so it scales with the insolation the same way
+/// the equilibrium temperature does.
+pub fn calculate_exospheric_temperature(insolation: EarthInsolation) -> Kelvin {
+    Kelvin::from(EARTH_EXOSPHERE_TEMPERATURE_K * f32::from(insolation).powf(0.25))
+}
+
+/// Call [Atmosphere::calculate_parameters] to get the values of an atmosphere
+/// made of the given gases.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Atmosphere {
+    /// Pressure of each gas on its own. Together they add up to the total pressure.
+    pub partial_pressures: Vec<(Gas, Bar)>,
+    /// At the surface.
+    pub total_pressure: Bar,
+    /// Average mass of the molecules, in g/mol. For the earth this is about 29.
+    pub mean_molecular_weight: f32,
+    /// Height over which the pressure drops by a factor of e (about 2.7).
+    pub scale_height: Kilometer,
+    /// `None` when the pressure is too low for water to be liquid at any temperature.
+    pub water_boiling_point: Option<Kelvin>,
+    /// Empty when humans can breathe the air without any help.
+    pub hazards: Vec<BreathingHazard>,
+}
+
+impl Atmosphere {
+    /// Gases with a partial pressure of 0 (or less) are left out.
+    pub fn calculate_parameters(
+        partial_pressures: Vec<(Gas, Bar)>,
+        surface_gravity: Gravity,
+        surface_temperature: Kelvin,
+    ) -> Atmosphere {
+        let partial_pressures: Vec<(Gas, Bar)> = partial_pressures
+            .into_iter()
+            .filter(|(_, pressure)| f32::from(*pressure) > 0.)
+            .collect();
+
+        let total_pressure: f32 = partial_pressures
+            .iter()
+            .map(|(_, pressure)| f32::from(*pressure))
+            .sum();
+        let mean_molecular_weight = if total_pressure > 0. {
+            partial_pressures
+                .iter()
+                .map(|(gas, pressure)| gas.molar_mass() * f32::from(*pressure))
+                .sum::<f32>()
+                / total_pressure
+        } else {
+            0.
+        };
+
+        let scale_height = if mean_molecular_weight > 0. {
+            GAS_CONSTANT * f32::from(surface_temperature)
+                / (mean_molecular_weight / 1000. * f32::from(surface_gravity) * EARTH_GRAVITY)
+                / 1000.
+        } else {
+            0.
+        };
+
+        let mut atmosphere = Atmosphere {
+            partial_pressures,
+            total_pressure: total_pressure.into(),
+            mean_molecular_weight,
+            scale_height: scale_height.into(),
+            water_boiling_point: calculate_water_boiling_point(total_pressure.into()),
+            hazards: Vec::new(),
+        };
+        atmosphere.hazards = BreathingHazard::calculate(&atmosphere);
+        atmosphere
+    }
+
+    /// Partial pressure of the given gas, 0 if it isn't in the atmosphere.
+    pub fn partial_pressure(&self, gas: Gas) -> Bar {
+        self.partial_pressures
+            .iter()
+            .find(|(g, _)| *g == gas)
+            .map(|(_, pressure)| *pressure)
+            .unwrap_or_else(|| 0.0.into())
+    }
+
+    pub fn is_breathable(&self) -> bool {
+        self.hazards.is_empty()
+    }
+
+    /// Roughly the atmosphere of the earth at sea level, leaving out water vapor.
+    pub fn earth_like_partial_pressures() -> Vec<(Gas, Bar)> {
+        vec![
+            (Gas::Nitrogen, 0.791.into()),
+            (Gas::Oxygen, 0.212.into()),
+            (Gas::Argon, 0.0095.into()),
+            (Gas::CarbonDioxide, 0.0004.into()),
+        ]
+    }
+}
+
+/// Uses the Clausius-Clapeyron relation.
+pub fn calculate_water_boiling_point(pressure: Bar) -> Option<Kelvin> {
+    let pressure: f32 = pressure.into();
+    if pressure < WATER_TRIPLE_POINT_PRESSURE_BAR {
+        return None;
+    }
+
+    let inverse_temperature = 1. / WATER_BOILING_POINT_K
+        - GAS_CONSTANT * (pressure / WATER_BOILING_POINT_PRESSURE_BAR).ln()
+            / WATER_HEAT_OF_VAPORIZATION;
+    Some(Kelvin::from(1. / inverse_temperature))
+}
+
+/// Reasons why humans can't breathe an atmosphere for long.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BreathingHazard {
+    /// Too little oxygen.
+    Hypoxia,
+    /// Too much oxygen.
+    OxygenToxicity,
+    CarbonDioxideToxicity,
+    NitrogenNarcosis,
+}
+
+impl BreathingHazard {
+    pub fn calculate(atmosphere: &Atmosphere) -> Vec<BreathingHazard> {
+        let pressure = |gas: Gas| f32::from(atmosphere.partial_pressure(gas));
+        let mut hazards = Vec::new();
+
+        if pressure(Gas::Oxygen) < MIN_OXYGEN_PRESSURE_BAR {
+            hazards.push(BreathingHazard::Hypoxia);
+        }
+        if pressure(Gas::Oxygen) > MAX_OXYGEN_PRESSURE_BAR {
+            hazards.push(BreathingHazard::OxygenToxicity);
+        }
+        if pressure(Gas::CarbonDioxide) > MAX_CARBON_DIOXIDE_PRESSURE_BAR {
+            hazards.push(BreathingHazard::CarbonDioxideToxicity);
+        }
+        if pressure(Gas::Nitrogen) > MAX_NITROGEN_PRESSURE_BAR {
+            hazards.push(BreathingHazard::NitrogenNarcosis);
+        }
+
+        hazards
+    }
+}
+
+impl Display for BreathingHazard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BreathingHazard::Hypoxia => write!(f, "not enough oxygen"),
+            BreathingHazard::OxygenToxicity => write!(f, "too much oxygen"),
+            BreathingHazard::CarbonDioxideToxicity => write!(f, "carbon dioxide poisoning"),
+            BreathingHazard::NitrogenNarcosis => write!(f, "nitrogen narcosis"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::atmosphere::*;
+
+    #[test]
+    fn test_earth_keeps_nitrogen_but_loses_hydrogen() {
+        let retention =
+            calculate_gas_retention(11.186.into(), calculate_exospheric_temperature(1.0.into()));
+        let retained = |gas: Gas| retention.iter().find(|r| r.gas == gas).unwrap().retained;
+
+        assert!(!retained(Gas::Hydrogen));
+        assert!(!retained(Gas::Helium));
+        assert!(retained(Gas::Water));
+        assert!(retained(Gas::Nitrogen));
+        assert_eq!(
+            1.0646143,
+            f32::from(calculate_thermal_velocity(Gas::Nitrogen, 1273.0.into()))
+        );
+    }
+
+    #[test]
+    fn test_earth_atmosphere() {
+        let atmosphere = Atmosphere::calculate_parameters(
+            Atmosphere::earth_like_partial_pressures(),
+            1.0.into(),
+            288.0.into(),
+        );
+
+        assert_eq!(1.0129, f32::from(atmosphere.total_pressure));
+        assert_eq!(28.966095, atmosphere.mean_molecular_weight);
+        assert_eq!(8.429308, f32::from(atmosphere.scale_height));
+        assert_eq!(
+            373.14017,
+            f32::from(atmosphere.water_boiling_point.unwrap())
+        );
+        assert!(atmosphere.is_breathable());
+    }
+}
@@ -0,0 +1,175 @@
+//! Two stars (or groups of stars) orbiting each other.
+//!
+//! Systems with more than two stars are almost always hierarchical: a close pair of stars,
+//! with a third star orbiting the pair from far away. This is modelled by letting one of the
+//! components of a binary be another binary, see [BinaryComponent::combine].
+
+use crate::units::*;
+use crate::MainSequenceStar;
+use serde::{Deserialize, Serialize};
+
+/// One side of a binary. Either a single star, or a pair of stars that are close together
+/// compared to the separation of the binary.
+#[derive(Copy, Clone)]
+pub struct BinaryComponent {
+    pub mass: SolarMass,
+    pub luminosity: SolarLuminosity,
+}
+
+impl BinaryComponent {
+    pub fn from_star(star: &MainSequenceStar) -> Self {
+        BinaryComponent {
+            mass: star.current_mass,
+            luminosity: star.luminosity,
+        }
+    }
+
+    /// Seen from far away, a pair of stars acts as one star with their combined mass
+    /// and luminosity.
+    pub fn combine(a: &BinaryComponent, b: &BinaryComponent) -> Self {
+        BinaryComponent {
+            mass: a.mass + b.mass,
+            luminosity: a.luminosity + b.luminosity,
+        }
+    }
+}
+
+/// Call [BinaryOrbit::calculate_parameters] to get the values of two components
+/// orbiting their common center of mass (the barycenter).
+///
+/// Planets can orbit one of the components (S-type orbits), as long as they stay close
+/// enough to it, or both components (P-type orbits, circumbinary), as long as they stay
+/// far enough away from them.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BinaryOrbit {
+    /// Average distance between the two components.
+    pub semi_major_axis: AstronomicalUnit,
+    /// 0 is a perfect circle, values approaching 1 are very elongated ellipses.
+    pub eccentricity: f32,
+    pub period: EarthYear,
+    /// Closest distance between the components.
+    pub periapsis: AstronomicalUnit,
+    /// Furthest distance between the components.
+    pub apoapsis: AstronomicalUnit,
+    /// Average distance of the primary to the barycenter.
+    pub primary_barycenter_distance: AstronomicalUnit,
+    /// Average distance of the secondary to the barycenter.
+    pub secondary_barycenter_distance: AstronomicalUnit,
+    /// Planets orbiting only the primary are stable within this distance from it.
+    pub primary_stable_orbit_limit: AstronomicalUnit,
+    /// Planets orbiting only the secondary are stable within this distance from it.
+    pub secondary_stable_orbit_limit: AstronomicalUnit,
+    /// Planets orbiting both components are stable beyond this distance from the barycenter.
+    pub circumbinary_stable_orbit_limit: AstronomicalUnit,
+    /// Habitable zone for planets orbiting both components.
+    pub circumbinary_habitable_zone_inner_edge: AstronomicalUnit,
+    pub circumbinary_habitable_zone_outer_edge: AstronomicalUnit,
+}
+
+impl BinaryOrbit {
+    /// Eccentricity is clamped between 0 (inclusive) and 1 (exclusive), because
+    /// anything else is not a closed orbit.
+    pub fn calculate_parameters(
+        primary: &BinaryComponent,
+        secondary: &BinaryComponent,
+        semi_major_axis: AstronomicalUnit,
+        eccentricity: f32,
+    ) -> BinaryOrbit {
+        let eccentricity = eccentricity.clamp(0., 0.999);
+
+        let combined = BinaryComponent::combine(primary, secondary);
+        // Fraction of the total mass that is in the secondary.
+        let secondary_fraction = secondary.mass / combined.mass;
+
+        BinaryOrbit {
+            semi_major_axis,
+            eccentricity,
+            period: calculate_orbital_period(semi_major_axis, combined.mass),
+            periapsis: semi_major_axis * (1. - eccentricity),
+            apoapsis: semi_major_axis * (1. + eccentricity),
+            primary_barycenter_distance: semi_major_axis * secondary_fraction,
+            secondary_barycenter_distance: semi_major_axis * (1. - secondary_fraction),
+            primary_stable_orbit_limit: calculate_s_type_stable_orbit_limit(
+                semi_major_axis,
+                eccentricity,
+                secondary_fraction,
+            ),
+            secondary_stable_orbit_limit: calculate_s_type_stable_orbit_limit(
+                semi_major_axis,
+                eccentricity,
+                1. - secondary_fraction,
+            ),
+            circumbinary_stable_orbit_limit: calculate_p_type_stable_orbit_limit(
+                semi_major_axis,
+                eccentricity,
+                secondary_fraction.min(1. - secondary_fraction),
+            ),
+            circumbinary_habitable_zone_inner_edge: calculate_habitable_zone_inner_edge(
+                combined.luminosity,
+            ),
+            circumbinary_habitable_zone_outer_edge: calculate_habitable_zone_outer_edge(
+                combined.luminosity,
+            ),
+        }
+    }
+
+    /// Whether planets in the inner part of the circumbinary habitable zone have stable orbits.
+    pub fn is_circumbinary_habitable_zone_stable(&self) -> bool {
+        self.circumbinary_stable_orbit_limit < self.circumbinary_habitable_zone_inner_edge
+    }
+}
+
+/// Largest stable orbit around one component of a binary, following
+/// [Holman & Wiegert 1999](https://doi.org/10.1086/300695).
+/// `other_mass_fraction` is the fraction of the total mass that is in the other component.
+pub fn calculate_s_type_stable_orbit_limit(
+    semi_major_axis: AstronomicalUnit,
+    eccentricity: f32,
+    other_mass_fraction: f32,
+) -> AstronomicalUnit {
+    let (e, mu) = (eccentricity, other_mass_fraction);
+    let factor = 0.464 - 0.380 * mu - 0.631 * e + 0.586 * mu * e + 0.150 * e.powi(2)
+        - 0.198 * mu * e.powi(2);
+
+    semi_major_axis * factor.max(0.)
+}
+
+/// Smallest stable orbit around both components of a binary, following
+/// [Holman & Wiegert 1999](https://doi.org/10.1086/300695).
+/// `lightest_mass_fraction` is the fraction of the total mass that is in the lightest component.
+pub fn calculate_p_type_stable_orbit_limit(
+    semi_major_axis: AstronomicalUnit,
+    eccentricity: f32,
+    lightest_mass_fraction: f32,
+) -> AstronomicalUnit {
+    let (e, mu) = (eccentricity, lightest_mass_fraction);
+    let factor = 1.60 + 5.10 * e - 2.22 * e.powi(2) + 4.12 * mu - 4.27 * e * mu - 5.09 * mu.powi(2)
+        + 4.61 * e.powi(2) * mu.powi(2);
+
+    semi_major_axis * factor
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binary::*;
+
+    #[test]
+    fn test_alpha_centauri() {
+        let component = |mass: f32, luminosity: f32| BinaryComponent {
+            mass: mass.into(),
+            luminosity: SolarLuminosity::new(luminosity),
+        };
+        let orbit = BinaryOrbit::calculate_parameters(
+            &component(1.1, 1.519),
+            &component(0.907, 0.5),
+            23.3.into(),
+            0.52,
+        );
+
+        assert_eq!(79.38897, f32::from(orbit.period));
+        assert_eq!(2.7546246, f32::from(orbit.primary_stable_orbit_limit));
+        assert_eq!(2.4659917, f32::from(orbit.secondary_stable_orbit_limit));
+        assert_eq!(86.79776, f32::from(orbit.circumbinary_stable_orbit_limit));
+        assert!(!orbit.is_circumbinary_habitable_zone_stable());
+    }
+}
@@ -0,0 +1,85 @@
+//! Brown dwarfs are too light to fuse hydrogen, so they don't have a main sequence.
+//! Instead they slowly cool down and get dimmer over their whole life.
+//!
+//! The cooling follows the relations of
+//! [Burrows & Liebert 1993](https://doi.org/10.1103/RevModPhys.65.301).
+
+use crate::units::*;
+use crate::MainSequenceStellarClass;
+use serde::{Deserialize, Serialize};
+
+/// Lighter objects can't even fuse deuterium, and are considered planets.
+pub const BROWN_DWARF_MIN_MASS_JUPITER: f32 = 13.;
+/// Heavier objects fuse hydrogen, and are stars.
+pub const BROWN_DWARF_MAX_MASS_JUPITER: f32 = 75.;
+
+/// Brown dwarfs all have about the same radius as jupiter, regardless of their mass.
+/// Heavier ones are compressed more by their own gravity.
+const BROWN_DWARF_RADIUS: SolarRadius = SolarRadius::new(0.1);
+/// Temperature of a brown dwarf that is a billion years old and weighs 0.05 solar masses.
+const REFERENCE_TEMPERATURE: Kelvin = Kelvin::new(1550.);
+const REFERENCE_MASS: SolarMass = SolarMass::new(0.05);
+const REFERENCE_AGE: Gigayear = Gigayear::new(1.);
+/// A million years.
+const MIN_AGE: Gigayear = Gigayear::new(0.001);
+/// While they are still burning deuterium, brown dwarfs are about as hot as the lightest stars.
+const MAX_TEMPERATURE: Kelvin = Kelvin::new(2800.);
+
+/// Call [BrownDwarf::calculate_parameters] to get the values of a brown dwarf.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct BrownDwarf {
+    pub mass: JupiterMass,
+    pub age_gigayears: Gigayear,
+    pub class: MainSequenceStellarClass,
+    pub radius: SolarRadius,
+    pub luminosity: SolarLuminosity,
+    pub temperature: Kelvin,
+}
+
+impl BrownDwarf {
+    /// Only valid for masses between [BROWN_DWARF_MIN_MASS_JUPITER]
+    /// and [BROWN_DWARF_MAX_MASS_JUPITER].
+    pub fn calculate_parameters(mass: JupiterMass, age_gigayears: Gigayear) -> BrownDwarf {
+        let radius = BROWN_DWARF_RADIUS;
+        let temperature = calculate_brown_dwarf_temperature(mass, age_gigayears);
+        let luminosity = calculate_black_body_luminosity(radius, temperature);
+
+        BrownDwarf {
+            mass,
+            age_gigayears,
+            class: MainSequenceStellarClass::calculate(temperature, Default::default()),
+            radius,
+            luminosity,
+            temperature,
+        }
+    }
+}
+
+/// Heavier brown dwarfs start out hotter, and all of them cool down as they age.
+pub fn calculate_brown_dwarf_temperature(mass: JupiterMass, age: Gigayear) -> Kelvin {
+    let mass = SolarMass::from(mass) / REFERENCE_MASS;
+    // Don't let the temperature go to infinity for newly formed brown dwarfs.
+    let age = age.max(MIN_AGE) / REFERENCE_AGE;
+
+    let temperature = REFERENCE_TEMPERATURE * age.powf(-0.32) * mass.powf(0.83);
+    temperature.min(MAX_TEMPERATURE)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::brown_dwarf::*;
+
+    #[test]
+    fn test_brown_dwarfs_cool_through_the_classes() {
+        let class = |mass, age| {
+            BrownDwarf::calculate_parameters(JupiterMass::new(mass), Gigayear::new(age))
+                .class
+                .spectral_class
+        };
+
+        assert_eq!(SpectralClass::M, class(70., 0.01));
+        assert_eq!(SpectralClass::L, class(70., 1.));
+        assert_eq!(SpectralClass::T, class(30., 5.));
+        assert_eq!(SpectralClass::Y, class(13., 10.));
+    }
+}
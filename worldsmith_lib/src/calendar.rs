@@ -0,0 +1,239 @@
+//! Calendars for the inhabitants of a planet, based on the length of its day, year and month.
+
+use crate::units::*;
+use std::fmt::{Display, Formatter};
+
+/// Leap rules are added until the calendar drifts less than a day in this many years.
+const ACCEPTABLE_DRIFT_YEARS: f64 = 10_000.;
+/// Nobody remembers rules that only apply once in this many years.
+const MAX_LEAP_CYCLE_YEARS: f64 = 10_000.;
+/// Without a moon to follow, use the same number of months as we do.
+const DEFAULT_MONTHS_PER_YEAR: u32 = 12;
+
+/// Call [Calendar::calculate_parameters] to design a calendar.
+#[derive(Clone)]
+pub struct Calendar {
+    /// Time from one noon to the next. Slightly longer than the rotation period,
+    /// because the planet moves around the star in the meantime.
+    pub solar_day: Hour,
+    /// Number of local (solar) days in a year.
+    pub days_per_year: f32,
+    /// Days in a year without leap days.
+    pub common_year_days: u32,
+    /// The part of a day that is left over every year. Negative when the common year
+    /// is slightly too long, in which case the leap rules skip days instead of adding them.
+    pub remainder: f32,
+    /// Keep the calendar in line with the seasons, like the leap years of
+    /// the gregorian calendar. Each rule applies once every so many years.
+    pub leap_rules: Vec<LeapRule>,
+    /// Number of local days from one full moon to the next. `None` without a moon.
+    pub days_per_month: Option<f32>,
+    pub months_per_year: Option<f32>,
+    /// The months of a common year.
+    pub months: Vec<CalendarMonth>,
+}
+
+impl Calendar {
+    /// The rotation period is relative to the stars, and the year is the orbital period
+    /// of the planet. For the earth, this gives a year of 365.256 days, a bit longer than the
+    /// 365.242 days of our calendar, which follows the seasons instead of the stars.
+    ///
+    /// Returns `None` when the planet rotates as slowly as it orbits the star, or slower,
+    /// because then the sun does not go around the sky anymore.
+    pub fn calculate_parameters(
+        rotation_period: Hour,
+        year: EarthDay,
+        synodic_month: Option<EarthDay>,
+    ) -> Option<Calendar> {
+        let rotation_period = f64::from(f32::from(rotation_period));
+        let year_hours = f64::from(f32::from(year)) * 24.;
+        if rotation_period <= 0. || rotation_period >= year_hours {
+            return None;
+        }
+
+        let solar_day = 1. / (1. / rotation_period - 1. / year_hours);
+        let days_per_year = year_hours / solar_day;
+        // A year always has at least one day, even when the sun barely goes around the sky.
+        let common_year_days = days_per_year.round().max(1.);
+        let remainder = days_per_year - common_year_days;
+
+        let days_per_month =
+            synodic_month.map(|month| f64::from(f32::from(month)) * 24. / solar_day);
+        let months_per_year = days_per_month.map(|month| days_per_year / month);
+
+        let month_count = months_per_year.map_or(DEFAULT_MONTHS_PER_YEAR, |months| {
+            months.round().max(1.) as u32
+        });
+
+        Some(Calendar {
+            solar_day: Hour::from(solar_day as f32),
+            days_per_year: days_per_year as f32,
+            common_year_days: common_year_days as u32,
+            remainder: remainder as f32,
+            leap_rules: calculate_leap_rules(remainder),
+            days_per_month: days_per_month.map(|days| days as f32),
+            months_per_year: months_per_year.map(|months| months as f32),
+            months: calculate_months(common_year_days as u32, month_count),
+        })
+    }
+
+    /// Average length of a year in the calendar, including leap days.
+    pub fn mean_year_days(&self) -> f32 {
+        (f64::from(self.common_year_days) + self.leap_days_per_year()) as f32
+    }
+
+    /// How many days the calendar is ahead (negative) or behind (positive) after the given
+    /// number of years, despite the leap rules.
+    pub fn drift_days(&self, years: u32) -> f32 {
+        ((f64::from(self.remainder) - self.leap_days_per_year()) * f64::from(years)) as f32
+    }
+
+    fn leap_days_per_year(&self) -> f64 {
+        self.leap_rules
+            .iter()
+            .map(|rule| f64::from(rule.days) / f64::from(rule.every_years))
+            .sum()
+    }
+
+    /// The months as comma separated values, to use in a spreadsheet.
+    /// Each leap rule gets a column with the days in the years it applies to. Every rule
+    /// applies to a multiple of the years of the previous one, so those years follow the
+    /// previous rules as well. Leap days are added to (or skipped from) the last month.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("Month,Days");
+        for rule in &self.leap_rules {
+            csv.push_str(&format!(",Every {} years", rule.every_years));
+        }
+        csv.push('\n');
+
+        let last = self.months.len().saturating_sub(1);
+        for (index, month) in self.months.iter().enumerate() {
+            csv.push_str(&format!("{},{}", month.name, month.days));
+            let mut days = month.days as i32;
+            for rule in &self.leap_rules {
+                if index == last {
+                    days += rule.days;
+                }
+                csv.push_str(&format!(",{}", days));
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
+
+/// Add or skip `days` (1 or -1) once every `every_years` years.
+/// When several rules apply to the same year, all of them are applied.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct LeapRule {
+    pub every_years: u32,
+    pub days: i32,
+}
+
+impl Display for LeapRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.days > 0 {
+            write!(f, "every {} years, add a day", self.every_years)
+        } else {
+            write!(f, "every {} years, skip a day", self.every_years)
+        }
+    }
+}
+
+/// Rules are added the same way as in the gregorian calendar: each one corrects the error
+/// left over by the previous ones, and applies to a multiple of the years of the previous one.
+/// That way the rules stay easy to remember, like "every 4 years, except every 100 years".
+pub fn calculate_leap_rules(remainder: f64) -> Vec<LeapRule> {
+    let mut rules = Vec::new();
+    let mut error = remainder;
+    let mut cycle = 1.;
+
+    while error.abs() * ACCEPTABLE_DRIFT_YEARS >= 1. {
+        let every_years = ((1. / error.abs()) / cycle).round().max(2.) * cycle;
+        if every_years > MAX_LEAP_CYCLE_YEARS {
+            break;
+        }
+
+        let days = error.signum() as i32;
+        rules.push(LeapRule {
+            every_years: every_years as u32,
+            days,
+        });
+        error -= f64::from(days) / every_years;
+        cycle = every_years;
+    }
+
+    rules
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CalendarMonth {
+    pub name: String,
+    pub days: u32,
+}
+
+/// Spreads the days over the months as evenly as possible,
+/// so that longer and shorter months alternate.
+pub fn calculate_months(days: u32, month_count: u32) -> Vec<CalendarMonth> {
+    let month_count = month_count.max(1);
+    let start_of =
+        |month: u32| (u64::from(days) * u64::from(month) / u64::from(month_count)) as u32;
+
+    (0..month_count)
+        .map(|month| CalendarMonth {
+            name: format!("Month {}", month + 1),
+            days: start_of(month + 1) - start_of(month),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::calendar::*;
+
+    #[test]
+    fn test_earth_calendar() {
+        let calendar =
+            Calendar::calculate_parameters(23.9345.into(), 365.256.into(), Some(29.530588.into()))
+                .unwrap();
+
+        assert_eq!(365, calendar.common_year_days);
+        assert_eq!(
+            vec![
+                LeapRule {
+                    every_years: 4,
+                    days: 1
+                },
+                LeapRule {
+                    every_years: 180,
+                    days: 1
+                }
+            ],
+            calendar.leap_rules
+        );
+        assert_eq!(12.368734, calendar.months_per_year.unwrap());
+        assert_eq!(12, calendar.months.len());
+        assert_eq!(0.032916334, calendar.drift_days(1000));
+        assert!(calendar
+            .to_csv()
+            .starts_with("Month,Days,Every 4 years,Every 180 years\nMonth 1,30,30,30\n"));
+        assert!(calendar.to_csv().ends_with("Month 12,31,32,33\n"));
+    }
+
+    #[test]
+    fn test_slowly_rotating_planet_has_at_least_one_day() {
+        let calendar = Calendar::calculate_parameters(200.0.into(), 10.0.into(), None).unwrap();
+
+        assert_eq!(1, calendar.common_year_days);
+        assert_eq!(
+            1,
+            calendar.months.iter().map(|month| month.days).sum::<u32>()
+        );
+    }
+
+    #[test]
+    fn test_tidally_locked_planet_has_no_calendar() {
+        assert!(Calendar::calculate_parameters(240.0.into(), 10.0.into(), None).is_none());
+    }
+}
@@ -0,0 +1,121 @@
+//! Temperature at the surface of a planet.
+
+use crate::orbit::Orbit;
+use crate::units::*;
+use crate::MainSequenceStar;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Optical depth of the atmosphere of the earth for the infrared light that the surface
+/// radiates. This warms the earth from 255 K to its actual 288 K.
+const EARTH_GREENHOUSE_OPTICAL_DEPTH: f32 = 0.84;
+
+/// Below this, water freezes.
+const MIN_HABITABLE_TEMPERATURE: Kelvin = Kelvin::new(273.15);
+/// Above this, there is little complex life on earth.
+const MAX_HABITABLE_TEMPERATURE: Kelvin = Kelvin::new(323.15);
+
+/// Call [Climate::calculate_parameters] to get the temperature of a planet.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Climate {
+    /// Fraction of the light of the star that is reflected back into space (0 to 1).
+    /// The earth is about 0.3.
+    pub albedo: f32,
+    /// Strength of the greenhouse effect, relative to that of the earth.
+    /// 0 is no atmosphere at all.
+    pub greenhouse_effect: f32,
+    /// Temperature when only heated by the star.
+    pub equilibrium_temperature: Kelvin,
+    /// Average temperature at the surface.
+    pub surface_temperature: Kelvin,
+    pub verdict: TemperatureVerdict,
+}
+
+impl Climate {
+    /// Albedo is clamped between 0 and 1, and the greenhouse effect can't be negative.
+    pub fn calculate_parameters(
+        star: &MainSequenceStar,
+        orbit: &Orbit,
+        albedo: f32,
+        greenhouse_effect: f32,
+    ) -> Climate {
+        let albedo = albedo.clamp(0., 1.);
+        let greenhouse_effect = greenhouse_effect.max(0.);
+
+        // The distance of a circular orbit that receives the same amount of light
+        // as this orbit, averaged over a whole year.
+        let mean_distance = orbit.semi_major_axis * (1. - orbit.eccentricity.powi(2)).powf(0.25);
+        let equilibrium_temperature =
+            calculate_equilibrium_temperature(star.luminosity, mean_distance, albedo);
+        let surface_temperature =
+            calculate_surface_temperature(equilibrium_temperature, greenhouse_effect);
+
+        Climate {
+            albedo,
+            greenhouse_effect,
+            equilibrium_temperature,
+            surface_temperature,
+            verdict: TemperatureVerdict::calculate(surface_temperature),
+        }
+    }
+}
+
+/// Uses a grey atmosphere, which absorbs all infrared light equally.
+/// The surface has to become hotter to radiate away the same amount of energy through it.
+pub fn calculate_surface_temperature(
+    equilibrium_temperature: Kelvin,
+    greenhouse_effect: f32,
+) -> Kelvin {
+    let optical_depth = greenhouse_effect * EARTH_GREENHOUSE_OPTICAL_DEPTH;
+    equilibrium_temperature * (1. + 0.75 * optical_depth).powf(0.25)
+}
+
+/// Whether the surface is comfortable for life as we know it.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TemperatureVerdict {
+    TooCold,
+    Habitable,
+    TooHot,
+}
+
+impl TemperatureVerdict {
+    pub fn calculate(surface_temperature: Kelvin) -> Self {
+        if surface_temperature < MIN_HABITABLE_TEMPERATURE {
+            TemperatureVerdict::TooCold
+        } else if surface_temperature > MAX_HABITABLE_TEMPERATURE {
+            TemperatureVerdict::TooHot
+        } else {
+            TemperatureVerdict::Habitable
+        }
+    }
+}
+
+impl Display for TemperatureVerdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemperatureVerdict::TooCold => write!(f, "too cold"),
+            TemperatureVerdict::Habitable => write!(f, "habitable"),
+            TemperatureVerdict::TooHot => write!(f, "too hot"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::climate::*;
+
+    #[test]
+    fn test_earth_climate() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let orbit = Orbit::calculate_parameters(&sun, 1.0.into(), 0.0167, 0.0.into());
+
+        let climate = Climate::calculate_parameters(&sun, &orbit, 0.3, 1.);
+
+        assert_eq!(254.84186, f32::from(climate.equilibrium_temperature));
+        assert_eq!(
+            14.800415,
+            f32::from(Celsius::from(climate.surface_temperature))
+        );
+        assert_eq!(TemperatureVerdict::Habitable, climate.verdict);
+    }
+}
@@ -0,0 +1,153 @@
+//! The color of a star, calculated from the light of a black body of the same temperature.
+//!
+//! The spectrum is integrated against the CIE 1931 color matching functions, which describe
+//! how the human eye responds to light, and then converted to sRGB for display on a screen.
+//! The color is as seen by an eye that is adapted to daylight (the D65 white point of sRGB),
+//! which is why the sun comes out as nearly white.
+
+use crate::units::{ColorRgb, Kelvin};
+
+/// Visible part of the spectrum, in nanometers.
+const WAVELENGTH_MIN_NM: f64 = 380.;
+const WAVELENGTH_MAX_NM: f64 = 780.;
+const WAVELENGTH_STEP_NM: f64 = 5.;
+
+/// Colder objects give off almost no visible light at all, and the fit of the color matching
+/// functions is not accurate enough to calculate what little color they have.
+/// They get the deep red color of an object at this temperature instead.
+const MIN_TEMPERATURE_K: f64 = 500.;
+
+/// Second radiation constant (h * c / k), in meter kelvin.
+const SECOND_RADIATION_CONSTANT: f64 = 1.438_776_9e-2;
+
+/// The color of the light of the star, at the brightness of a white surface.
+/// Colors that are too saturated to show on a screen are clipped, so very hot stars
+/// come out almost white. This is close to how stars look to the naked eye.
+pub fn calculate_perceived_color(temperature: Kelvin) -> ColorRgb {
+    match calculate_linear_rgb(temperature) {
+        Some((r, g, b)) => to_srgb(r.min(1.), g.min(1.), b.min(1.)),
+        None => ColorRgb::new(0, 0, 0),
+    }
+}
+
+/// The same hue as [calculate_perceived_color], scaled up until one of the channels is
+/// at its maximum. Exaggerates the differences between stars, which makes it the most
+/// useful color for drawing them.
+pub fn calculate_display_color(temperature: Kelvin) -> ColorRgb {
+    match calculate_linear_rgb(temperature) {
+        Some((r, g, b)) => {
+            let max = r.max(g).max(b);
+            to_srgb(r / max, g / max, b / max)
+        }
+        None => ColorRgb::new(0, 0, 0),
+    }
+}
+
+/// Linear sRGB values of the black body spectrum, scaled to a luminance of 1.
+/// Returns `None` when the object does not emit any light.
+fn calculate_linear_rgb(temperature: Kelvin) -> Option<(f64, f64, f64)> {
+    let temperature = f64::from(f32::from(temperature));
+    if temperature <= 0. {
+        return None;
+    }
+    let temperature = temperature.max(MIN_TEMPERATURE_K);
+
+    let (mut x, mut y, mut z) = (0., 0., 0.);
+    let mut wavelength = WAVELENGTH_MIN_NM;
+    while wavelength <= WAVELENGTH_MAX_NM {
+        let intensity = planck(wavelength, temperature);
+        x += intensity * color_matching_x(wavelength);
+        y += intensity * color_matching_y(wavelength);
+        z += intensity * color_matching_z(wavelength);
+        wavelength += WAVELENGTH_STEP_NM;
+    }
+
+    if y <= 0. || y.is_nan() {
+        return None;
+    }
+    let (x, z) = (x / y, z / y);
+    let y = 1.;
+
+    // Negative values are outside of the sRGB gamut.
+    let r = (3.2406 * x - 1.5372 * y - 0.4986 * z).max(0.);
+    let g = (-0.9689 * x + 1.8758 * y + 0.0415 * z).max(0.);
+    let b = (0.0557 * x - 0.2040 * y + 1.0570 * z).max(0.);
+
+    Some((r, g, b))
+}
+
+/// Spectral radiance of a black body, without the constant factors,
+/// because only the relative intensity between wavelengths matters here.
+fn planck(wavelength_nm: f64, temperature: f64) -> f64 {
+    let wavelength = wavelength_nm * 1e-9;
+    1. / (wavelength.powi(5)
+        * ((SECOND_RADIATION_CONSTANT / (wavelength * temperature)).exp() - 1.))
+}
+
+// The color matching functions use the multi-lobe fit of
+// [Wyman, Sloan & Shirley 2013](https://jcgt.org/published/0002/02/01/).
+
+fn color_matching_x(wavelength: f64) -> f64 {
+    1.056 * piecewise_gaussian(wavelength, 599.8, 37.9, 31.0)
+        + 0.362 * piecewise_gaussian(wavelength, 442.0, 16.0, 26.7)
+        - 0.065 * piecewise_gaussian(wavelength, 501.1, 20.4, 26.2)
+}
+
+fn color_matching_y(wavelength: f64) -> f64 {
+    0.821 * piecewise_gaussian(wavelength, 568.8, 46.9, 40.5)
+        + 0.286 * piecewise_gaussian(wavelength, 530.9, 16.3, 31.1)
+}
+
+fn color_matching_z(wavelength: f64) -> f64 {
+    1.217 * piecewise_gaussian(wavelength, 437.0, 11.8, 36.0)
+        + 0.681 * piecewise_gaussian(wavelength, 459.0, 26.0, 13.8)
+}
+
+/// Gaussian with a different width on each side of the peak.
+fn piecewise_gaussian(x: f64, peak: f64, width_below: f64, width_above: f64) -> f64 {
+    let width = if x < peak { width_below } else { width_above };
+    (-0.5 * ((x - peak) / width).powi(2)).exp()
+}
+
+/// Applies the sRGB transfer function ("gamma") to linear values between 0 and 1.
+fn to_srgb(r: f64, g: f64, b: f64) -> ColorRgb {
+    let encode = |value: f64| {
+        let value = if value <= 0.003_130_8 {
+            12.92 * value
+        } else {
+            1.055 * value.powf(1. / 2.4) - 0.055
+        };
+        (value.clamp(0., 1.) * 255.).round() as u8
+    };
+
+    ColorRgb::new(encode(r), encode(g), encode(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::color::*;
+
+    #[test]
+    fn test_star_colors() {
+        assert_eq!(
+            ColorRgb::new(255, 241, 234),
+            calculate_display_color(5776.0.into())
+        );
+        assert_eq!(
+            ColorRgb::new(255, 141, 21),
+            calculate_display_color(2000.0.into())
+        );
+        assert_eq!(
+            ColorRgb::new(162, 187, 255),
+            calculate_display_color(30_000.0.into())
+        );
+    }
+
+    #[test]
+    fn test_cold_objects_are_black() {
+        assert_eq!(
+            ColorRgb::new(0, 0, 0),
+            calculate_perceived_color(0.0.into())
+        );
+    }
+}
@@ -0,0 +1,340 @@
+//! What happens to a star after it leaves the main sequence.
+//!
+//! The durations of the stages are fractions of the main sequence lifetime, fitted to the sun.
+//! Stars below roughly 8 solar masses swell into red giants and end as white dwarfs,
+//! heavier stars become red supergiants and end as neutron stars or black holes.
+
+use crate::units::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::ops::{Div, Mul};
+
+/// Stars below this mass become white dwarfs, stars above it explode as supernovae.
+const SUPERNOVA_MIN_MASS: SolarMass = SolarMass::new(8.);
+/// Stars above this mass leave a black hole instead of a neutron star.
+const BLACK_HOLE_MIN_MASS: SolarMass = SolarMass::new(20.);
+/// Stars below this mass are fully convective, and never become giants.
+/// They slowly turn into white dwarfs instead.
+/// Their lifetime is far longer than the current age of the universe, so none of them
+/// have actually done this yet.
+const RED_GIANT_MIN_MASS: SolarMass = SolarMass::new(0.5);
+
+// Durations of the stages, as fractions of the main sequence lifetime.
+const SUBGIANT_DURATION: f32 = 0.07;
+const RED_GIANT_DURATION: f32 = 0.05;
+const HORIZONTAL_BRANCH_DURATION: f32 = 0.01;
+const RED_SUPERGIANT_DURATION: f32 = 0.1;
+
+/// Temperature at which subgiants become red giants.
+const RED_GIANT_BASE_TEMPERATURE: Kelvin = Kelvin::new(5000.);
+/// Temperature at the tip of the red giant branch.
+const RED_GIANT_TIP_TEMPERATURE: Kelvin = Kelvin::new(3200.);
+/// Luminosity at the tip of the red giant branch, for sun-like stars.
+const RED_GIANT_TIP_LUMINOSITY: SolarLuminosity = SolarLuminosity::new(2500.);
+/// Horizontal branch stars of sun-like mass sit in the "red clump".
+const HORIZONTAL_BRANCH_TEMPERATURE: Kelvin = Kelvin::new(5000.);
+const HORIZONTAL_BRANCH_LUMINOSITY: SolarLuminosity = SolarLuminosity::new(50.);
+const RED_SUPERGIANT_TEMPERATURE: Kelvin = Kelvin::new(3500.);
+
+/// Mass of the white dwarf left by the lightest stars that evolve, in the
+/// initial-final mass relation.
+const WHITE_DWARF_BASE_MASS: SolarMass = SolarMass::new(0.394);
+/// Heavier white dwarfs collapse (the Chandrasekhar limit is 1.4 solar masses).
+const WHITE_DWARF_MAX_MASS: SolarMass = SolarMass::new(1.35);
+/// A typical white dwarf, about as large as the earth.
+const WHITE_DWARF_REFERENCE_MASS: SolarMass = SolarMass::new(0.6);
+const WHITE_DWARF_REFERENCE_RADIUS: SolarRadius = SolarRadius::new(0.0126);
+/// Luminosity of the typical white dwarf after cooling down for a billion years.
+const WHITE_DWARF_REFERENCE_LUMINOSITY: SolarLuminosity = SolarLuminosity::new(0.001);
+/// A million years.
+const WHITE_DWARF_MIN_COOLING_AGE: Gigayear = Gigayear::new(0.001);
+
+const NEUTRON_STAR_MASS: SolarMass = SolarMass::new(1.4);
+/// 12 km.
+const NEUTRON_STAR_RADIUS: SolarRadius = SolarRadius::new(1.725e-5);
+/// Surface temperature of a neutron star that is a million years old.
+const NEUTRON_STAR_TEMPERATURE: Kelvin = Kelvin::new(1_000_000.);
+const BLACK_HOLE_MIN_REMNANT_MASS: SolarMass = SolarMass::new(3.);
+/// Schwarzschild radius of a black hole of 1 solar mass (2.95 km).
+const SOLAR_SCHWARZSCHILD_RADIUS: SolarRadius = SolarRadius::new(4.245e-6);
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum EvolutionaryStage {
+    #[default]
+    MainSequence,
+    /// Too light to ever fuse hydrogen, so it never gets to the main sequence.
+    /// See [crate::brown_dwarf].
+    BrownDwarf,
+    /// Hydrogen fusion has moved from the core to a shell around it. The star starts to expand.
+    Subgiant,
+    RedGiant,
+    /// Helium fusion in the core.
+    HorizontalBranch,
+    RedSupergiant,
+    WhiteDwarf,
+    NeutronStar,
+    BlackHole,
+}
+
+impl EvolutionaryStage {
+    /// Remnants no longer have any fusion going on.
+    pub fn is_remnant(&self) -> bool {
+        matches!(
+            self,
+            EvolutionaryStage::WhiteDwarf
+                | EvolutionaryStage::NeutronStar
+                | EvolutionaryStage::BlackHole
+        )
+    }
+
+    pub fn luminosity_class(&self) -> LuminosityClass {
+        match self {
+            EvolutionaryStage::MainSequence | EvolutionaryStage::BrownDwarf => {
+                LuminosityClass::MainSequence
+            }
+            EvolutionaryStage::Subgiant => LuminosityClass::Subgiant,
+            EvolutionaryStage::RedGiant | EvolutionaryStage::HorizontalBranch => {
+                LuminosityClass::Giant
+            }
+            EvolutionaryStage::RedSupergiant => LuminosityClass::Supergiant,
+            EvolutionaryStage::WhiteDwarf
+            | EvolutionaryStage::NeutronStar
+            | EvolutionaryStage::BlackHole => LuminosityClass::WhiteDwarf,
+        }
+    }
+}
+
+impl Display for EvolutionaryStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            EvolutionaryStage::MainSequence => "main sequence",
+            EvolutionaryStage::BrownDwarf => "brown dwarf",
+            EvolutionaryStage::Subgiant => "subgiant",
+            EvolutionaryStage::RedGiant => "red giant",
+            EvolutionaryStage::HorizontalBranch => "horizontal branch",
+            EvolutionaryStage::RedSupergiant => "red supergiant",
+            EvolutionaryStage::WhiteDwarf => "white dwarf",
+            EvolutionaryStage::NeutronStar => "neutron star",
+            EvolutionaryStage::BlackHole => "black hole",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The Yerkes (MK) luminosity class. Tells how large a star is for its temperature.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum LuminosityClass {
+    Supergiant,
+    Giant,
+    Subgiant,
+    #[default]
+    MainSequence,
+    WhiteDwarf,
+}
+
+impl Display for LuminosityClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let symbol = match self {
+            LuminosityClass::Supergiant => "I",
+            LuminosityClass::Giant => "III",
+            LuminosityClass::Subgiant => "IV",
+            LuminosityClass::MainSequence => "V",
+            LuminosityClass::WhiteDwarf => "D",
+        };
+        write!(f, "{}", symbol)
+    }
+}
+
+/// Parameters of a star that has left the main sequence.
+pub struct EvolvedState {
+    pub stage: EvolutionaryStage,
+    /// Stars lose a lot of mass before ending up as a remnant.
+    pub mass: SolarMass,
+    pub radius: SolarRadius,
+    pub luminosity: SolarLuminosity,
+    pub temperature: Kelvin,
+}
+
+/// Only valid for stars that are older than their main sequence lifetime.
+pub fn calculate_evolved_state(
+    initial_mass: SolarMass,
+    age: Gigayear,
+    max_age: Gigayear,
+) -> EvolvedState {
+    // Fraction of the main sequence lifetime that has passed since leaving the main sequence.
+    let post_main_sequence_fraction = (age / max_age - 1.).max(0.);
+
+    let terminal_luminosity = SolarLuminosity::calculate_at_age(initial_mass, 1.);
+    let terminal_temperature = calculate_stellar_temperature_at_age(initial_mass, 1.);
+
+    if initial_mass >= SUPERNOVA_MIN_MASS {
+        if post_main_sequence_fraction < RED_SUPERGIANT_DURATION {
+            let t = post_main_sequence_fraction / RED_SUPERGIANT_DURATION;
+            return giant_state(
+                EvolutionaryStage::RedSupergiant,
+                initial_mass,
+                log_interpolate(terminal_luminosity, terminal_luminosity * 2., t),
+                log_interpolate(terminal_temperature, RED_SUPERGIANT_TEMPERATURE, t),
+            );
+        }
+
+        let remnant_age = max_age * (post_main_sequence_fraction - RED_SUPERGIANT_DURATION);
+        return if initial_mass >= BLACK_HOLE_MIN_MASS {
+            black_hole_state(initial_mass)
+        } else {
+            neutron_star_state(remnant_age)
+        };
+    }
+
+    let mut stage_start = 0.;
+
+    if initial_mass >= RED_GIANT_MIN_MASS {
+        let subgiant_end_luminosity = terminal_luminosity * 1.5;
+        let red_giant_tip_luminosity = RED_GIANT_TIP_LUMINOSITY.max(terminal_luminosity * 3.);
+
+        if post_main_sequence_fraction < SUBGIANT_DURATION {
+            let t = post_main_sequence_fraction / SUBGIANT_DURATION;
+            return giant_state(
+                EvolutionaryStage::Subgiant,
+                initial_mass,
+                log_interpolate(terminal_luminosity, subgiant_end_luminosity, t),
+                log_interpolate(
+                    terminal_temperature,
+                    RED_GIANT_BASE_TEMPERATURE.min(terminal_temperature),
+                    t,
+                ),
+            );
+        }
+        stage_start += SUBGIANT_DURATION;
+
+        if post_main_sequence_fraction < stage_start + RED_GIANT_DURATION {
+            let t = (post_main_sequence_fraction - stage_start) / RED_GIANT_DURATION;
+            return giant_state(
+                EvolutionaryStage::RedGiant,
+                initial_mass,
+                log_interpolate(subgiant_end_luminosity, red_giant_tip_luminosity, t),
+                log_interpolate(
+                    RED_GIANT_BASE_TEMPERATURE.min(terminal_temperature),
+                    RED_GIANT_TIP_TEMPERATURE,
+                    t,
+                ),
+            );
+        }
+        stage_start += RED_GIANT_DURATION;
+
+        if post_main_sequence_fraction < stage_start + HORIZONTAL_BRANCH_DURATION {
+            return giant_state(
+                EvolutionaryStage::HorizontalBranch,
+                initial_mass,
+                HORIZONTAL_BRANCH_LUMINOSITY.max(terminal_luminosity * 3.),
+                HORIZONTAL_BRANCH_TEMPERATURE,
+            );
+        }
+        stage_start += HORIZONTAL_BRANCH_DURATION;
+    }
+
+    let cooling_age = max_age * (post_main_sequence_fraction - stage_start);
+    white_dwarf_state(initial_mass, cooling_age)
+}
+
+fn giant_state(
+    stage: EvolutionaryStage,
+    mass: SolarMass,
+    luminosity: SolarLuminosity,
+    temperature: Kelvin,
+) -> EvolvedState {
+    EvolvedState {
+        stage,
+        mass,
+        radius: SolarRadius::calculate_from_luminosity(luminosity, temperature),
+        luminosity,
+        temperature,
+    }
+}
+
+/// The mass of the white dwarf follows the initial-final mass relation of
+/// [Kalirai et al. 2008](https://arxiv.org/abs/0706.3894).
+/// White dwarfs get smaller as they get heavier,
+/// and they cool down following Mestel's law.
+fn white_dwarf_state(initial_mass: SolarMass, cooling_age: Gigayear) -> EvolvedState {
+    let mass = (initial_mass * 0.109 + WHITE_DWARF_BASE_MASS).min(WHITE_DWARF_MAX_MASS);
+    let relative_mass = mass / WHITE_DWARF_REFERENCE_MASS;
+    let radius = WHITE_DWARF_REFERENCE_RADIUS * relative_mass.powf(-1. / 3.);
+    // A young white dwarf is incredibly hot, so don't let the luminosity go to infinity.
+    let cooling_age = cooling_age.max(WHITE_DWARF_MIN_COOLING_AGE) / GIGAYEAR;
+    let luminosity = WHITE_DWARF_REFERENCE_LUMINOSITY * relative_mass * cooling_age.powf(-1.4);
+
+    EvolvedState {
+        stage: EvolutionaryStage::WhiteDwarf,
+        mass,
+        radius,
+        luminosity,
+        temperature: calculate_stellar_temperature(radius, luminosity),
+    }
+}
+
+fn neutron_star_state(age: Gigayear) -> EvolvedState {
+    let radius = NEUTRON_STAR_RADIUS;
+    // Cools down slowly, after a very hot start.
+    let age_megayears = (age / GIGAYEAR * 1000.).max(1.);
+    let temperature = NEUTRON_STAR_TEMPERATURE * age_megayears.powf(-0.25);
+
+    EvolvedState {
+        stage: EvolutionaryStage::NeutronStar,
+        mass: NEUTRON_STAR_MASS,
+        radius,
+        luminosity: calculate_black_body_luminosity(radius, temperature),
+        temperature,
+    }
+}
+
+/// Black holes don't emit any light of their own.
+fn black_hole_state(initial_mass: SolarMass) -> EvolvedState {
+    let mass = (initial_mass * 0.3).max(BLACK_HOLE_MIN_REMNANT_MASS);
+
+    EvolvedState {
+        stage: EvolutionaryStage::BlackHole,
+        mass,
+        radius: SOLAR_SCHWARZSCHILD_RADIUS * (mass / SUN_MASS),
+        luminosity: SolarLuminosity::new(0.),
+        temperature: Kelvin::new(0.),
+    }
+}
+
+/// Interpolates between the two values on a logarithmic scale.
+/// `t` goes from 0 to 1.
+fn log_interpolate<U>(from: U, to: U, t: f32) -> U
+where
+    U: Copy + Div<Output = f32> + Mul<f32, Output = U>,
+{
+    from * (to / from).powf(t)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evolution::*;
+
+    #[test]
+    fn test_sun_like_star_stages() {
+        let stage = |age| {
+            calculate_evolved_state(SolarMass::new(1.), Gigayear::new(age), Gigayear::new(10.))
+                .stage
+        };
+
+        assert_eq!(EvolutionaryStage::Subgiant, stage(10.5));
+        assert_eq!(EvolutionaryStage::RedGiant, stage(11.));
+        assert_eq!(EvolutionaryStage::HorizontalBranch, stage(11.25));
+        assert_eq!(EvolutionaryStage::WhiteDwarf, stage(12.));
+    }
+
+    #[test]
+    fn test_massive_star_remnants() {
+        let remnant = |mass| {
+            calculate_evolved_state(SolarMass::new(mass), Gigayear::new(1.), Gigayear::new(0.01))
+                .stage
+        };
+
+        assert_eq!(EvolutionaryStage::NeutronStar, remnant(10.));
+        assert_eq!(EvolutionaryStage::BlackHole, remnant(30.));
+    }
+}
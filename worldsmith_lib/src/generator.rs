@@ -0,0 +1,337 @@
+//! Random star systems. The same seed always gives the same system,
+//! so that a system can be shared by sharing its seed.
+
+use crate::giant::GiantPlanet;
+use crate::moon::{Moon, MoonHost};
+use crate::orbit::Orbit;
+use crate::planet::{PlanetComposition, TerrestrialPlanet};
+use crate::system::{StarSystem, SystemMoon, SystemStar};
+use crate::units::*;
+use crate::{
+    MainSequenceStar, MAIN_SEQUENCE_MIN_MASS_SOLAR, STAR_MAX_AGE_GIGAYEARS, STAR_MIN_AGE_GIGAYEARS,
+};
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Masses (in solar masses) where the slope of the initial mass function changes.
+const KROUPA_BREAKS: [f64; 2] = [0.08, 0.5];
+/// The number of stars falls off with mass^-slope, one slope for each range between the breaks.
+const KROUPA_SLOPES: [f64; 3] = [0.3, 1.3, 2.3];
+/// Heavier stars leave the main sequence before they are [STAR_MIN_AGE_GIGAYEARS] old.
+const MAX_STAR_MASS_SOLAR: f32 = 30.;
+/// Each planet orbits between this many times further out than the previous one.
+/// Our solar system is roughly spaced like this.
+const ORBIT_SPACING: (f32, f32) = (1.4, 2.2);
+const MAX_ECCENTRICITY: f32 = 0.1;
+/// Relative to the reference plane of the system.
+const MAX_INCLINATION_DEG: f32 = 3.;
+const MAX_AXIAL_TILT_DEG: f32 = 40.;
+const TERRESTRIAL_MASS_RANGE_EARTH: (f32, f32) = (0.05, 5.);
+/// From about the mass of neptune to a few times jupiter.
+const GIANT_MASS_RANGE_JUPITER: (f32, f32) = (0.03, 5.);
+const GIANT_ROTATION_PERIOD_RANGE_HOURS: (f32, f32) = (9., 20.);
+const MOON_MASS_RANGE_EARTH: (f32, f32) = (0.0001, 0.025);
+const MAX_TERRESTRIAL_MOONS: u32 = 2;
+const MAX_GIANT_MOONS: u32 = 6;
+
+/// Generates a system with a single star, with rocky planets inside the frost line,
+/// giants outside of it, and moons around both.
+///
+/// The star is picked with [calculate_initial_mass], so most systems have a red dwarf,
+/// like in our own galaxy. The star is always on the main sequence.
+pub fn generate_system(seed: u64) -> StarSystem {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let name = format!("Seed {}", seed);
+
+    let mass = calculate_initial_mass(
+        &mut rng,
+        MAIN_SEQUENCE_MIN_MASS_SOLAR.into(),
+        MAX_STAR_MASS_SOLAR.into(),
+    );
+    let max_age = calculate_maximum_age(mass, SolarLuminosity::calculate(mass));
+    let age = Gigayear::new(random_range(
+        &mut rng,
+        STAR_MIN_AGE_GIGAYEARS,
+        f32::from(max_age).min(STAR_MAX_AGE_GIGAYEARS),
+    ));
+    let star = MainSequenceStar::calculate_parameters(mass, age);
+
+    let mut system = StarSystem::new(&name, SystemStar::new(&name, star.clone()));
+
+    let frost_line: f32 = star.frost_line.into();
+    let outer_system_limit: f32 = star.outer_system_limit.into();
+    let mut distance = f32::from(star.inner_system_limit) * random_range(&mut rng, 1., 2.);
+    let mut planet_letter = b'b';
+
+    while distance < outer_system_limit {
+        let planet_name = format!("{} {}", name, planet_letter as char);
+        let orbit = Orbit::calculate_parameters(
+            &star,
+            distance.into(),
+            random_range(&mut rng, 0., MAX_ECCENTRICITY),
+            random_range(&mut rng, 0., MAX_INCLINATION_DEG).into(),
+        );
+
+        if distance < frost_line {
+            let (min_mass, max_mass) = TERRESTRIAL_MASS_RANGE_EARTH;
+            let iron = random_range(&mut rng, 0.15, 0.5);
+            let planet = TerrestrialPlanet::calculate_parameters(
+                random_log_range(&mut rng, min_mass, max_mass).into(),
+                PlanetComposition::new(iron, 1. - iron, 0.),
+            );
+            let axial_tilt = random_range(&mut rng, 0., MAX_AXIAL_TILT_DEG);
+
+            let moon_count = random_count(&mut rng, MAX_TERRESTRIAL_MOONS);
+            let moons = generate_moons(
+                &mut rng,
+                &planet_name,
+                &MoonHost::from(&planet),
+                &orbit,
+                moon_count,
+            );
+
+            system.add_planet(&planet_name, 0, planet, &orbit, axial_tilt.into());
+            if let Some(added) = system.planets.last_mut() {
+                added.moons = moons;
+            }
+        } else {
+            // Lighter stars have lighter disks to form giants from.
+            let (min_mass, max_mass) = GIANT_MASS_RANGE_JUPITER;
+            let max_mass = (max_mass * f32::from(mass)).clamp(min_mass, max_mass);
+            let (min_rotation, max_rotation) = GIANT_ROTATION_PERIOD_RANGE_HOURS;
+            let giant = GiantPlanet::calculate_parameters(
+                random_log_range(&mut rng, min_mass, max_mass).into(),
+                random_range(&mut rng, min_rotation, max_rotation).into(),
+                &star,
+                orbit.semi_major_axis,
+            );
+
+            let moon_count = random_count(&mut rng, MAX_GIANT_MOONS);
+            let moons = generate_moons(
+                &mut rng,
+                &planet_name,
+                &MoonHost::from(&giant),
+                &orbit,
+                moon_count,
+            );
+
+            system.add_giant(&planet_name, 0, &giant, &orbit);
+            if let Some(added) = system.giants.last_mut() {
+                added.moons = moons;
+            }
+        }
+
+        let (min_spacing, max_spacing) = ORBIT_SPACING;
+        distance *= random_range(&mut rng, min_spacing, max_spacing);
+        planet_letter += 1;
+    }
+
+    system
+}
+
+/// Moons are placed between twice the roche limit and half the stable orbit limit,
+/// and named after the planet in order of distance, like the moons of jupiter.
+/// Planets that have no room for stable moons get none.
+fn generate_moons(
+    rng: &mut impl RngCore,
+    planet_name: &str,
+    host: &MoonHost,
+    host_orbit: &Orbit,
+    count: u32,
+) -> Vec<SystemMoon> {
+    let mut moons: Vec<(TerrestrialPlanet, f32)> = vec![];
+
+    for _ in 0..count {
+        let (min_mass, max_mass) = MOON_MASS_RANGE_EARTH;
+        let ice = random_range(rng, 0., 0.5);
+        let body = TerrestrialPlanet::calculate_parameters(
+            random_log_range(rng, min_mass, max_mass).into(),
+            PlanetComposition::new(0.1, 1. - ice, ice),
+        );
+
+        // The limits don't depend on the distance.
+        let limits = Moon::calculate_parameters(&body, 1.0.into(), host, host_orbit);
+        let inner = 2. * f32::from(limits.roche_limit);
+        let outer = 0.5 * f32::from(limits.stable_orbit_limit);
+        if inner < outer {
+            moons.push((body, random_log_range(rng, inner, outer)));
+        }
+    }
+
+    moons.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    moons
+        .into_iter()
+        .enumerate()
+        .map(|(index, (body, distance))| SystemMoon {
+            name: format!("{} {}", planet_name, roman_numeral(index + 1)),
+            notes: String::new(),
+            moon: Moon::calculate_parameters(&body, distance.into(), host, host_orbit),
+            body,
+            semi_major_axis: distance.into(),
+        })
+        .collect()
+}
+
+/// Picks a mass from the initial mass function of
+/// [Kroupa 2001](https://arxiv.org/abs/astro-ph/0009005), which gives how many stars
+/// form with each mass. Light stars are far more common than heavy ones:
+/// about three out of four stars are red dwarfs.
+pub fn calculate_initial_mass(
+    rng: &mut impl RngCore,
+    min_mass: SolarMass,
+    max_mass: SolarMass,
+) -> SolarMass {
+    let min_mass = f64::from(f32::from(min_mass));
+    let max_mass = f64::from(f32::from(max_mass));
+
+    // Lower edge, upper edge, slope and number of stars of each part of the function,
+    // limited to the requested masses.
+    let mut segments: Vec<(f64, f64, f64, f64)> = vec![];
+    // Keeps the function continuous at the breaks.
+    let mut scale = 1.;
+    for (index, &slope) in KROUPA_SLOPES.iter().enumerate() {
+        let lower = if index == 0 {
+            0.
+        } else {
+            let lower = KROUPA_BREAKS[index - 1];
+            scale *= lower.powf(slope - KROUPA_SLOPES[index - 1]);
+            lower
+        };
+        let upper = KROUPA_BREAKS.get(index).copied().unwrap_or(f64::INFINITY);
+
+        let lower = lower.max(min_mass);
+        let upper = upper.min(max_mass);
+        if lower < upper {
+            let exponent = 1. - slope;
+            let count = scale * (upper.powf(exponent) - lower.powf(exponent)) / exponent;
+            segments.push((lower, upper, slope, count));
+        }
+    }
+
+    let total: f64 = segments.iter().map(|(_, _, _, count)| count).sum();
+    let mut pick = random_fraction(rng) * total;
+    let fraction = random_fraction(rng);
+
+    for &(lower, upper, slope, count) in &segments {
+        if pick < count {
+            // Inverse of the cumulative distribution within this part.
+            let exponent = 1. - slope;
+            let mass = (lower.powf(exponent)
+                + fraction * (upper.powf(exponent) - lower.powf(exponent)))
+            .powf(1. / exponent);
+            return SolarMass::from(mass as f32);
+        }
+        pick -= count;
+    }
+
+    SolarMass::from(max_mass as f32)
+}
+
+/// Between 0 (inclusive) and 1 (exclusive). Only uses the raw output of the generator,
+/// so that a seed keeps giving the same system with newer versions of the rand crates.
+fn random_fraction(rng: &mut impl RngCore) -> f64 {
+    (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+fn random_range(rng: &mut impl RngCore, min: f32, max: f32) -> f32 {
+    min + (max - min) * random_fraction(rng) as f32
+}
+
+/// Small values are as likely as large ones, for values that span several orders of magnitude.
+fn random_log_range(rng: &mut impl RngCore, min: f32, max: f32) -> f32 {
+    random_range(rng, min.ln(), max.ln()).exp()
+}
+
+/// From 0 up to and including the maximum.
+fn random_count(rng: &mut impl RngCore, max: u32) -> u32 {
+    (random_fraction(rng) * f64::from(max + 1)) as u32
+}
+
+fn roman_numeral(mut number: usize) -> String {
+    const NUMERALS: [(usize, &str); 9] = [
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            roman.push_str(numeral);
+            number -= value;
+        }
+    }
+
+    roman
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evolution::EvolutionaryStage;
+    use crate::generator::*;
+
+    #[test]
+    fn test_same_seed_gives_same_system() {
+        let system = ron::to_string(&generate_system(42)).unwrap();
+
+        assert_eq!(system, ron::to_string(&generate_system(42)).unwrap());
+        assert_ne!(system, ron::to_string(&generate_system(43)).unwrap());
+    }
+
+    #[test]
+    fn test_planets_are_placed_by_zone() {
+        for seed in 0..20 {
+            let system = generate_system(seed);
+            let star = &system.primary_star().star;
+            let frost_line = star.frost_line;
+
+            assert_eq!(EvolutionaryStage::MainSequence, star.stage);
+            assert!(
+                MainSequenceStar::try_calculate_parameters(star.mass, star.age_gigayears).is_ok()
+            );
+
+            assert!(system
+                .planets
+                .iter()
+                .all(|planet| planet.orbit.semi_major_axis < frost_line));
+            assert!(system
+                .giants
+                .iter()
+                .all(|giant| giant.orbit.semi_major_axis >= frost_line));
+            assert!(system
+                .planets
+                .iter()
+                .flat_map(|planet| &planet.moons)
+                .all(|moon| moon.moon.is_orbit_stable()));
+        }
+    }
+
+    #[test]
+    fn test_most_stars_are_red_dwarfs() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let red_dwarfs = (0..1000)
+            .map(|_| {
+                calculate_initial_mass(
+                    &mut rng,
+                    MAIN_SEQUENCE_MIN_MASS_SOLAR.into(),
+                    MAX_STAR_MASS_SOLAR.into(),
+                )
+            })
+            .filter(|&mass| mass < SolarMass::new(0.5))
+            .count();
+
+        assert_eq!(772, red_dwarfs);
+    }
+
+    #[test]
+    fn test_roman_numeral() {
+        assert_eq!("XIV", roman_numeral(14));
+    }
+}
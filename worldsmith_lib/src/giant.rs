@@ -0,0 +1,223 @@
+use crate::units::*;
+use crate::MainSequenceStar;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Lighter giants are mostly made of water, ammonia and methane ices, like uranus and neptune.
+/// Heavier ones are mostly hydrogen and helium, like jupiter and saturn.
+const ICE_GIANT_MAX_MASS: JupiterMass = JupiterMass::new(0.15);
+
+/// Moment of inertia factor (I / MR²) of jupiter. Lower means more mass towards the center.
+const GAS_GIANT_MOMENT_OF_INERTIA_FACTOR: f32 = 0.254;
+/// Moment of inertia factor of neptune.
+const ICE_GIANT_MOMENT_OF_INERTIA_FACTOR: f32 = 0.23;
+
+/// Gravitational parameter (G * M) of jupiter, in m³/s².
+const JUPITER_GRAVITATIONAL_PARAMETER: f32 = 1.266_865e17;
+/// In meters.
+const JUPITER_RADIUS_M: f32 = 69_911_000.;
+
+/// Call [GiantPlanet::calculate_parameters] to get the values of a semi realistic
+/// gas giant or ice giant.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GiantPlanet {
+    pub mass: JupiterMass,
+    pub kind: GiantKind,
+    /// Length of a day.
+    pub rotation_period: Hour,
+    /// Mean radius.
+    pub radius: JupiterRadius,
+    /// Giants bulge out at the equator because they rotate so fast.
+    pub equatorial_radius: JupiterRadius,
+    pub polar_radius: JupiterRadius,
+    /// (equatorial radius - polar radius) / equatorial radius.
+    /// 0 is a perfect sphere, jupiter is about 0.065.
+    pub oblateness: f32,
+    pub density: EarthDensity,
+    /// At the cloud tops.
+    pub surface_gravity: Gravity,
+    pub escape_velocity: KilometerPerSecond,
+    /// Temperature when only heated by the star, without any internal heat.
+    pub equilibrium_temperature: Kelvin,
+    pub class: SudarskyClass,
+}
+
+impl GiantPlanet {
+    /// The equilibrium temperature, and so the class, depends on the distance to the star.
+    pub fn calculate_parameters(
+        mass: JupiterMass,
+        rotation_period: Hour,
+        star: &MainSequenceStar,
+        distance: AstronomicalUnit,
+    ) -> GiantPlanet {
+        let kind = GiantKind::calculate(mass);
+        let radius = calculate_giant_radius(mass);
+        let oblateness = calculate_oblateness(mass, radius, rotation_period, kind);
+
+        let earth_mass = EarthMass::from(mass);
+        let earth_radius = EarthRadius::from(radius);
+
+        // Gives the same volume as a sphere with the mean radius.
+        let equatorial_radius = radius * (1. + oblateness / 3.);
+        let polar_radius = radius * (1. - 2. * oblateness / 3.);
+
+        // The albedo of a giant depends on its class, so it is left out here.
+        let equilibrium_temperature =
+            calculate_equilibrium_temperature(star.luminosity, distance, 0.);
+
+        GiantPlanet {
+            mass,
+            kind,
+            rotation_period,
+            radius,
+            equatorial_radius,
+            polar_radius,
+            oblateness,
+            density: EarthDensity::calculate(earth_mass, earth_radius),
+            surface_gravity: Gravity::calculate(earth_mass, earth_radius),
+            escape_velocity: calculate_escape_velocity(earth_mass, earth_radius),
+            equilibrium_temperature,
+            class: SudarskyClass::calculate(equilibrium_temperature),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum GiantKind {
+    IceGiant,
+    GasGiant,
+}
+
+impl GiantKind {
+    pub fn calculate(mass: JupiterMass) -> Self {
+        if mass < ICE_GIANT_MAX_MASS {
+            GiantKind::IceGiant
+        } else {
+            GiantKind::GasGiant
+        }
+    }
+}
+
+impl Display for GiantKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GiantKind::IceGiant => write!(f, "ice giant"),
+            GiantKind::GasGiant => write!(f, "gas giant"),
+        }
+    }
+}
+
+/// The classification of [Sudarsky et al. 2000](https://arxiv.org/abs/astro-ph/9910504),
+/// based on which kind of clouds form in the atmosphere.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum SudarskyClass {
+    /// Ammonia clouds, like jupiter and saturn.
+    I,
+    /// Water clouds. Very reflective.
+    II,
+    /// Too hot for clouds. Very dark blue.
+    III,
+    /// Alkali metals in the atmosphere, absorbing nearly all light.
+    IV,
+    /// Silicate clouds, glowing red from the heat.
+    V,
+}
+
+impl SudarskyClass {
+    pub fn calculate(temperature: Kelvin) -> Self {
+        match temperature {
+            kelvin if kelvin < Kelvin::new(150.) => SudarskyClass::I,
+            kelvin if kelvin < Kelvin::new(250.) => SudarskyClass::II,
+            kelvin if kelvin < Kelvin::new(800.) => SudarskyClass::III,
+            kelvin if kelvin < Kelvin::new(1400.) => SudarskyClass::IV,
+            _ => SudarskyClass::V,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SudarskyClass::I => "ammonia clouds",
+            SudarskyClass::II => "water clouds",
+            SudarskyClass::III => "cloudless",
+            SudarskyClass::IV => "alkali metals",
+            SudarskyClass::V => "silicate clouds",
+        }
+    }
+}
+
+impl Display for SudarskyClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Uses the mass-radius relations of [Chen & Kipping 2017](https://arxiv.org/abs/1603.08614).
+/// Above roughly 0.4 jupiter masses, adding mass no longer makes a planet larger,
+/// because it compresses the planet under its own gravity.
+/// Very heavy giants are even slightly smaller than lighter ones.
+///
+/// The relations are scaled to give jupiter its actual radius. They are fitted to a lot of
+/// hot jupiters, which are puffed up by the heat of their star.
+pub fn calculate_giant_radius(mass: JupiterMass) -> JupiterRadius {
+    let chen_kipping_radius = |mass: EarthMass| {
+        let mass: f32 = mass.into();
+        if mass < 131.6 {
+            0.808 * mass.powf(0.589)
+        } else {
+            17.74 * mass.powf(-0.044)
+        }
+    };
+
+    JupiterRadius::from(
+        chen_kipping_radius(mass.into()) / chen_kipping_radius(JupiterMass::new(1.).into()),
+    )
+}
+
+/// Uses the Darwin-Radau relation, which relates the flattening of a rotating planet
+/// to its moment of inertia.
+pub fn calculate_oblateness(
+    mass: JupiterMass,
+    radius: JupiterRadius,
+    rotation_period: Hour,
+    kind: GiantKind,
+) -> f32 {
+    let moment_of_inertia_factor = match kind {
+        GiantKind::IceGiant => ICE_GIANT_MOMENT_OF_INERTIA_FACTOR,
+        GiantKind::GasGiant => GAS_GIANT_MOMENT_OF_INERTIA_FACTOR,
+    };
+
+    let angular_velocity = 2. * std::f32::consts::PI / (f32::from(rotation_period) * 3600.);
+    let radius = f32::from(radius) * JUPITER_RADIUS_M;
+    // Ratio of the centrifugal acceleration to the gravity at the equator.
+    let rotation_parameter = angular_velocity.powi(2) * radius.powi(3)
+        / (f32::from(mass) * JUPITER_GRAVITATIONAL_PARAMETER);
+
+    let eta = 2.5 * (1. - 1.5 * moment_of_inertia_factor);
+    (2.5 * rotation_parameter / (1. + eta.powi(2))).min(0.5)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::giant::*;
+
+    #[test]
+    fn test_jupiter() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let jupiter =
+            GiantPlanet::calculate_parameters(JupiterMass::new(1.), 9.925.into(), &sun, 5.2.into());
+
+        assert_eq!(GiantKind::GasGiant, jupiter.kind);
+        assert_eq!(SudarskyClass::I, jupiter.class);
+        assert_eq!(1., f32::from(jupiter.radius));
+        assert_eq!(0.061423052, jupiter.oblateness);
+        assert_eq!(2.6396365, f32::from(jupiter.surface_gravity));
+    }
+
+    #[test]
+    fn test_neptune_is_an_ice_giant() {
+        assert_eq!(
+            GiantKind::IceGiant,
+            GiantKind::calculate(JupiterMass::new(0.054))
+        );
+    }
+}
@@ -1,69 +1,310 @@
+pub mod atmosphere;
+pub mod binary;
+pub mod brown_dwarf;
+pub mod calendar;
+pub mod climate;
+pub mod color;
+pub mod evolution;
+pub mod generator;
+pub mod giant;
+pub mod moon;
+pub mod orbit;
+pub mod planet;
+pub mod project;
+pub mod seasons;
+pub mod system;
+pub mod tidal;
+pub mod unit_system;
 pub mod units;
 
+use crate::brown_dwarf::{BrownDwarf, BROWN_DWARF_MAX_MASS_JUPITER, BROWN_DWARF_MIN_MASS_JUPITER};
+use crate::color::{calculate_display_color, calculate_perceived_color};
+use crate::evolution::{calculate_evolved_state, EvolutionaryStage, LuminosityClass};
 use crate::units::*;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
+/// Lightest object that is calculated as a star, in solar masses: the lightest brown dwarf.
+/// Anything lighter can't fuse anything, and is a planet.
+pub const STAR_MIN_MASS_SOLAR: f32 = BROWN_DWARF_MIN_MASS_JUPITER * SOLAR_MASSES_PER_JUPITER_MASS;
+/// Lightest star that fuses hydrogen and stays on the main sequence, in solar masses.
+/// Anything lighter is a brown dwarf.
+pub const MAIN_SEQUENCE_MIN_MASS_SOLAR: f32 =
+    BROWN_DWARF_MAX_MASS_JUPITER * SOLAR_MASSES_PER_JUPITER_MASS;
+/// Heaviest star that is calculated, in solar masses. Heavier stars shine so brightly that
+/// the light pushes their outer layers away (the Eddington limit), so they don't stay
+/// on the main sequence like lighter stars do.
+pub const STAR_MAX_MASS_SOLAR: f32 = 94.;
+/// Younger stars are still forming.
+pub const STAR_MIN_AGE_GIGAYEARS: f32 = 0.001;
+/// Nothing can be older than the universe itself.
+pub const STAR_MAX_AGE_GIGAYEARS: f32 = 13.8;
+
 /// Call [calculate_parameters] to get the values of a semi realistic star.
-/// TODO (Wybe 2022-07-03): Change the f32 into new unit types, so that unit conversions are always correct.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MainSequenceStar {
     pub class: MainSequenceStellarClass,
+    /// Mass of the star when it formed.
     pub mass: SolarMass,
-    // age_gigayears: f32,
-    pub max_age_gigayears: f32,
+    /// Stars lose a lot of mass after they leave the main sequence.
+    /// Use this for anything that orbits the star.
+    #[serde(default)]
+    pub current_mass: SolarMass,
+    #[serde(default)]
+    pub stage: EvolutionaryStage,
+    #[serde(default)]
+    pub age_gigayears: Gigayear,
+    /// Age at which the star leaves the main sequence.
+    pub max_age_gigayears: Gigayear,
+    /// Fraction of the main sequence lifetime that has passed.
+    /// 0 is a newly formed star (zero-age main sequence), 1 is a star at the end of
+    /// the main sequence (terminal-age main sequence).
+    /// Larger than 1 when the star is older than its maximum age.
+    #[serde(default)]
+    pub lifetime_fraction: f32,
     pub radius: SolarRadius,
     pub luminosity: SolarLuminosity,
     pub density: SolarDensity,
     pub temperature: Kelvin,
-    // TODO: mention what this color means exactly. Because it is not truly what the eye would see.
+    /// Color for drawing the star. Exaggerated compared to what the eye would see,
+    /// see [color::calculate_display_color].
     pub color: ColorRgb,
+    /// Color of the star as it would look to the naked eye,
+    /// see [color::calculate_perceived_color].
+    #[serde(default)]
+    pub perceived_color: ColorRgb,
+    pub habitable_zone_inner_edge: AstronomicalUnit,
+    pub habitable_zone_outer_edge: AstronomicalUnit,
+    pub frost_line: AstronomicalUnit,
+    /// Closest distance to the star at which planets can form.
+    pub inner_system_limit: AstronomicalUnit,
+    /// Furthest distance from the star at which planets can form.
+    pub outer_system_limit: AstronomicalUnit,
 }
 
 impl MainSequenceStar {
-    /// TODO (Wybe 2022-07-03): Somehow incorporate brown dwarfs, and stars that are no longer in the main sequence.
     /// A Gyr (gigayear) is 1_000_000_000 years
-    pub fn calculate_parameters(mass: SolarMass, age_gigayears: f32) -> MainSequenceStar {
-        let radius = SolarRadius::calculate(mass);
-        let luminosity = SolarLuminosity::calculate(mass);
-        let density = SolarDensity::calculate(mass, radius);
-        let maximum_age = calculate_maximum_age_gigayears(mass, luminosity);
-        let temperature = calculate_stellar_temperature(radius, luminosity);
+    ///
+    /// Radius, luminosity and temperature evolve with the age of the star.
+    /// Stars grow larger and brighter as they age. The sun for example was about 30% dimmer
+    /// when it formed than it is now.
+    ///
+    /// Objects that are too light to fuse hydrogen are calculated as a [BrownDwarf].
+    ///
+    /// The mass and age are not checked, use [MainSequenceStar::try_calculate_parameters]
+    /// for input that might be out of range.
+    pub fn calculate_parameters(mass: SolarMass, age_gigayears: Gigayear) -> MainSequenceStar {
+        // The lifetime is based on the luminosity averaged over the main sequence,
+        // which is what the mass-luminosity relation gives.
+        let maximum_age = calculate_maximum_age(mass, SolarLuminosity::calculate(mass));
+        let lifetime_fraction = age_gigayears / maximum_age;
+
+        let (stage, current_mass, radius, luminosity, temperature) =
+            if JupiterMass::from(mass) < JupiterMass::new(BROWN_DWARF_MAX_MASS_JUPITER) {
+                let brown_dwarf = BrownDwarf::calculate_parameters(mass.into(), age_gigayears);
+
+                (
+                    EvolutionaryStage::BrownDwarf,
+                    mass,
+                    brown_dwarf.radius,
+                    brown_dwarf.luminosity,
+                    brown_dwarf.temperature,
+                )
+            } else if lifetime_fraction <= 1. {
+                let luminosity = SolarLuminosity::calculate_at_age(mass, lifetime_fraction);
+                let temperature = calculate_stellar_temperature_at_age(mass, lifetime_fraction);
+                let radius = SolarRadius::calculate_from_luminosity(luminosity, temperature);
+
+                (
+                    EvolutionaryStage::MainSequence,
+                    mass,
+                    radius,
+                    luminosity,
+                    temperature,
+                )
+            } else {
+                let evolved = calculate_evolved_state(mass, age_gigayears, maximum_age);
+
+                (
+                    evolved.stage,
+                    evolved.mass,
+                    evolved.radius,
+                    evolved.luminosity,
+                    evolved.temperature,
+                )
+            };
+        let density = SolarDensity::calculate(current_mass, radius);
 
-        let class = MainSequenceStellarClass::calculate(temperature);
-        let color = class.color();
+        let class = MainSequenceStellarClass::calculate(temperature, stage.luminosity_class());
+
+        let habitable_zone_inner_edge = calculate_habitable_zone_inner_edge(luminosity);
+        let habitable_zone_outer_edge = calculate_habitable_zone_outer_edge(luminosity);
+        let frost_line = calculate_frost_line(luminosity);
+        let inner_system_limit = calculate_inner_system_limit(current_mass);
+        let outer_system_limit = calculate_outer_system_limit(current_mass);
 
         MainSequenceStar {
             class,
             mass,
+            current_mass,
+            stage,
+            age_gigayears,
+            max_age_gigayears: maximum_age,
+            lifetime_fraction,
             radius,
             luminosity,
             density,
-            max_age_gigayears: maximum_age,
             temperature,
-            // todo: calculate the color of the star.
-            color,
+            color: calculate_display_color(temperature),
+            perceived_color: calculate_perceived_color(temperature),
+            habitable_zone_inner_edge,
+            habitable_zone_outer_edge,
+            frost_line,
+            inner_system_limit,
+            outer_system_limit,
+        }
+    }
+
+    /// Like [MainSequenceStar::calculate_parameters], but returns an error instead of
+    /// a meaningless star when the mass or age is outside of the range that the
+    /// calculations are made for. See [STAR_MIN_MASS_SOLAR] and the other limits.
+    pub fn try_calculate_parameters(
+        mass: SolarMass,
+        age_gigayears: Gigayear,
+    ) -> Result<MainSequenceStar, StarError> {
+        let solar_mass: f32 = mass.into();
+        let gigayears: f32 = age_gigayears.into();
+
+        if solar_mass.is_nan() || gigayears.is_nan() {
+            Err(StarError::NotANumber)
+        } else if solar_mass < STAR_MIN_MASS_SOLAR {
+            Err(StarError::MassBelowDeuteriumBurningLimit(solar_mass))
+        } else if solar_mass > STAR_MAX_MASS_SOLAR {
+            Err(StarError::MassAboveEddingtonLimit(solar_mass))
+        } else if gigayears < STAR_MIN_AGE_GIGAYEARS {
+            Err(StarError::AgeBeforeFormation(gigayears))
+        } else if gigayears > STAR_MAX_AGE_GIGAYEARS {
+            Err(StarError::AgeBeyondUniverse(gigayears))
+        } else {
+            Ok(MainSequenceStar::calculate_parameters(mass, age_gigayears))
+        }
+    }
+
+    /// Stars that are older than their maximum age are no longer on the main sequence.
+    pub fn is_past_main_sequence(&self) -> bool {
+        !matches!(
+            self.stage,
+            EvolutionaryStage::MainSequence | EvolutionaryStage::BrownDwarf
+        )
+    }
+
+    pub fn is_brown_dwarf(&self) -> bool {
+        self.stage == EvolutionaryStage::BrownDwarf
+    }
+
+    /// The stellar class, or the kind of remnant for neutron stars and black holes,
+    /// which don't have a stellar class.
+    pub fn classification(&self) -> String {
+        match self.stage {
+            EvolutionaryStage::NeutronStar | EvolutionaryStage::BlackHole => self.stage.to_string(),
+            _ => self.class.to_string(),
         }
     }
 }
 
-#[derive(Clone)]
+/// Why a star could not be calculated, see [MainSequenceStar::try_calculate_parameters].
+/// Masses are in solar masses, ages in giga years.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StarError {
+    /// Lighter than [STAR_MIN_MASS_SOLAR].
+    MassBelowDeuteriumBurningLimit(f32),
+    /// Heavier than [STAR_MAX_MASS_SOLAR].
+    MassAboveEddingtonLimit(f32),
+    /// Younger than [STAR_MIN_AGE_GIGAYEARS].
+    AgeBeforeFormation(f32),
+    /// Older than [STAR_MAX_AGE_GIGAYEARS].
+    AgeBeyondUniverse(f32),
+    NotANumber,
+}
+
+impl Display for StarError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StarError::MassBelowDeuteriumBurningLimit(mass) => write!(
+                f,
+                "A mass of {} {} is too light to fuse anything, it should be at least {:.4} {}",
+                mass,
+                SolarMass::SYMBOL,
+                STAR_MIN_MASS_SOLAR,
+                SolarMass::SYMBOL
+            ),
+            StarError::MassAboveEddingtonLimit(mass) => write!(
+                f,
+                "A mass of {} {} is too heavy to hold together, it should be at most {} {}",
+                mass,
+                SolarMass::SYMBOL,
+                STAR_MAX_MASS_SOLAR,
+                SolarMass::SYMBOL
+            ),
+            StarError::AgeBeforeFormation(age) => write!(
+                f,
+                "A star of {} {} is still forming, it should be at least {} {} old",
+                age,
+                Gigayear::SYMBOL,
+                STAR_MIN_AGE_GIGAYEARS,
+                Gigayear::SYMBOL
+            ),
+            StarError::AgeBeyondUniverse(age) => write!(
+                f,
+                "A star of {} {} is older than the universe, it should be at most {} {} old",
+                age,
+                Gigayear::SYMBOL,
+                STAR_MAX_AGE_GIGAYEARS,
+                Gigayear::SYMBOL
+            ),
+            StarError::NotANumber => write!(f, "The mass and age should be numbers"),
+        }
+    }
+}
+
+impl std::error::Error for StarError {}
+
+/// The number in the class of a white dwarf is this divided by its temperature.
+const WHITE_DWARF_TEMPERATURE_INDEX_K: f32 = 50_400.;
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct MainSequenceStellarClass {
     pub spectral_class: SpectralClass,
     /// Extra subdivision of spectral class.
     /// Ranges from 0 to 9 inclusive, where 0 is hottest, and 0 is the coolest.
+    /// For white dwarfs this is the temperature index 50400 / T instead, which goes above 9
+    /// for the coolest ones.
     pub subdivision: f32,
+    #[serde(default)]
+    pub luminosity_class: LuminosityClass,
 }
 
 impl MainSequenceStellarClass {
     /// Temperature in kelvin.
-    pub fn calculate(temperature: Kelvin) -> Self {
+    pub fn calculate(temperature: Kelvin, luminosity_class: LuminosityClass) -> Self {
         let spectral_class = SpectralClass::calculate(temperature);
         let kelvin: f32 = temperature.into();
 
+        if luminosity_class == LuminosityClass::WhiteDwarf {
+            return MainSequenceStellarClass {
+                spectral_class,
+                subdivision: WHITE_DWARF_TEMPERATURE_INDEX_K / kelvin,
+                luminosity_class,
+            };
+        }
+
         // TODO (Wybe 2022-07-03): Remove these magic numbers. this is actually just range mapping onto the temperature range of that class.
         let subdivision = 10.
             * match spectral_class {
-                SpectralClass::M => 1. - ((kelvin - 2000.) / 1700.),
+                SpectralClass::Y => 1. - ((kelvin - 250.) / 250.),
+                SpectralClass::T => 1. - ((kelvin - 500.) / 800.),
+                SpectralClass::L => 1. - ((kelvin - 1300.) / 1100.),
+                SpectralClass::M => 1. - ((kelvin - 2400.) / 1300.),
                 SpectralClass::K => 1. - ((kelvin - 3700.) / 1500.),
                 SpectralClass::G => 1. - ((kelvin - 5200.) / 800.),
                 SpectralClass::F => 1. - ((kelvin - 6000.) / 1500.),
@@ -74,27 +315,69 @@ impl MainSequenceStellarClass {
 
         MainSequenceStellarClass {
             spectral_class,
-            // TODO (Wybe 2022-07-03): Calculate subdivision
-            subdivision,
+            // Objects hotter or colder than the range of their class still get a valid subdivision.
+            subdivision: subdivision.clamp(0., 9.9),
+            luminosity_class,
         }
     }
+}
 
-    /// TODO: interpolate colors?
-    pub fn color(&self) -> ColorRgb {
-        match self.spectral_class {
-            SpectralClass::M => ColorRgb::new(255, 204, 111),
-            SpectralClass::K => ColorRgb::new(255, 210, 161),
-            SpectralClass::G => ColorRgb::new(255, 244, 234),
-            SpectralClass::F => ColorRgb::new(248, 247, 255),
-            SpectralClass::A => ColorRgb::new(202, 215, 255),
-            SpectralClass::B => ColorRgb::new(170, 191, 255),
-            SpectralClass::O => ColorRgb::new(155, 176, 255),
+impl Display for MainSequenceStellarClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.luminosity_class == LuminosityClass::WhiteDwarf {
+            // The letter after the D is the kind of atmosphere, which isn't calculated.
+            // Most white dwarfs have a hydrogen atmosphere (A), so all of them get that.
+            write!(f, "{}A{:.1}", self.luminosity_class, self.subdivision)
+        } else if matches!(
+            self.spectral_class,
+            SpectralClass::L | SpectralClass::T | SpectralClass::Y
+        ) {
+            // Brown dwarfs don't get a luminosity class, like "T6.0".
+            write!(f, "{}{:.1}", self.spectral_class, self.subdivision)
+        } else {
+            write!(
+                f,
+                "{}{:.1}{}",
+                self.spectral_class, self.subdivision, self.luminosity_class
+            )
         }
     }
 }
 
-impl Display for MainSequenceStellarClass {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}{:.1}", self.spectral_class, self.subdivision)
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_star_out_of_range() {
+        assert!(MainSequenceStar::try_calculate_parameters(1.0.into(), 4.6.into()).is_ok());
+        assert_eq!(
+            Some(StarError::MassBelowDeuteriumBurningLimit(0.)),
+            MainSequenceStar::try_calculate_parameters(0.0.into(), 4.6.into()).err()
+        );
+        assert_eq!(
+            Some(StarError::MassAboveEddingtonLimit(10_000.)),
+            MainSequenceStar::try_calculate_parameters(10_000.0.into(), 4.6.into()).err()
+        );
+        assert_eq!(
+            Some(StarError::AgeBeforeFormation(0.)),
+            MainSequenceStar::try_calculate_parameters(1.0.into(), 0.0.into()).err()
+        );
+        assert_eq!(
+            Some(StarError::AgeBeyondUniverse(20.)),
+            MainSequenceStar::try_calculate_parameters(1.0.into(), 20.0.into()).err()
+        );
+        assert_eq!(
+            Some(StarError::NotANumber),
+            MainSequenceStar::try_calculate_parameters(f32::NAN.into(), 4.6.into()).err()
+        );
+    }
+
+    #[test]
+    fn test_white_dwarf() {
+        let white_dwarf = MainSequenceStar::calculate_parameters(1.0.into(), 12.0.into());
+
+        assert_eq!("DA5.2", white_dwarf.classification());
+        assert_eq!(20.12, f32::from(white_dwarf.outer_system_limit));
     }
 }
@@ -0,0 +1,165 @@
+//! Moons orbiting a planet, and the tides they cause.
+//!
+//! Values are scaled to the earth and its moon where possible, so that the results are easy to
+//! compare to what we know.
+
+use crate::giant::GiantPlanet;
+use crate::orbit::Orbit;
+use crate::planet::TerrestrialPlanet;
+use crate::units::*;
+use serde::{Deserialize, Serialize};
+
+/// Mass of our moon.
+const MOON_MASS: EarthMass = EarthMass::new(0.0123);
+/// Average distance between the earth and our moon.
+const MOON_DISTANCE: Kilometer = Kilometer::new(384_400.);
+/// Orbital period of our moon, relative to the stars.
+const MOON_SIDEREAL_PERIOD: EarthDay = EarthDay::new(27.3217);
+const DAY: EarthDay = EarthDay::new(1.);
+
+/// Moons that orbit the same way the planet rotates are only stable in
+/// the inner half of the hill sphere.
+const STABLE_HILL_SPHERE_FRACTION: f32 = 0.5;
+/// For a moon that is held together by its own gravity, without any strength of its own.
+const ROCHE_LIMIT_FACTOR: f32 = 2.44;
+
+/// The planet that a moon orbits. Can be made from both terrestrial planets and giants.
+#[derive(Copy, Clone)]
+pub struct MoonHost {
+    pub mass: EarthMass,
+    pub radius: EarthRadius,
+    pub density: EarthDensity,
+}
+
+impl From<&TerrestrialPlanet> for MoonHost {
+    fn from(planet: &TerrestrialPlanet) -> Self {
+        MoonHost {
+            mass: planet.mass,
+            radius: planet.radius,
+            density: planet.density,
+        }
+    }
+}
+
+impl From<&GiantPlanet> for MoonHost {
+    fn from(giant: &GiantPlanet) -> Self {
+        MoonHost {
+            mass: giant.mass.into(),
+            radius: giant.radius.into(),
+            density: giant.density,
+        }
+    }
+}
+
+/// Call [Moon::calculate_parameters] to get the values of a moon orbiting a planet.
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Moon {
+    /// Average distance between the centers of the planet and the moon.
+    pub semi_major_axis: Kilometer,
+    /// Time for one orbit, relative to the stars.
+    pub sidereal_period: EarthDay,
+    /// Time from one full moon to the next, as seen from the planet.
+    /// This is longer than the sidereal period, because the planet moves around the star
+    /// in the meantime. `None` when the moon takes longer to orbit the planet than the planet
+    /// takes to orbit the star, in which case it would not stay with the planet anyway.
+    pub synodic_period: Option<EarthDay>,
+    /// The moon can't come closer to the planet than this, or it would be torn apart.
+    pub roche_limit: Kilometer,
+    /// The moon can't go further from the planet than this, or the star would pull it away.
+    pub stable_orbit_limit: Kilometer,
+    /// Strength of the tides on the planet, relative to those of our moon on the earth.
+    pub tidal_force: f32,
+    /// How large the moon looks from the surface of the planet. Our moon is about half a degree.
+    pub angular_size: Degree,
+}
+
+impl Moon {
+    pub fn calculate_parameters(
+        body: &TerrestrialPlanet,
+        semi_major_axis: Kilometer,
+        host: &MoonHost,
+        host_orbit: &Orbit,
+    ) -> Moon {
+        let host_radius = Kilometer::from(host.radius);
+
+        // Kepler's third law, scaled to our moon.
+        let mass_ratio = (host.mass + body.mass) / (EARTH_MASS + MOON_MASS);
+        let sidereal_period =
+            MOON_SIDEREAL_PERIOD * ((semi_major_axis / MOON_DISTANCE).powi(3) / mass_ratio).sqrt();
+
+        Moon {
+            semi_major_axis,
+            sidereal_period,
+            synodic_period: calculate_synodic_period(sidereal_period, host_orbit.period_days),
+            roche_limit: host_radius * ROCHE_LIMIT_FACTOR * (host.density / body.density).cbrt(),
+            stable_orbit_limit: calculate_hill_sphere_radius(host.mass, host_orbit)
+                * STABLE_HILL_SPHERE_FRACTION,
+            // Tides are caused by the difference in gravity between the near and far side
+            // of the planet, which falls off with the cube of the distance.
+            tidal_force: (body.mass / MOON_MASS)
+                * (host.radius / EARTH_RADIUS)
+                * (MOON_DISTANCE / semi_major_axis).powi(3),
+            angular_size: Degree::from_radians(
+                2. * (Kilometer::from(body.radius) / (semi_major_axis - host_radius)).atan(),
+            ),
+        }
+    }
+
+    /// Whether the moon is far enough from the planet to not be torn apart,
+    /// and close enough to not be pulled away by the star.
+    pub fn is_orbit_stable(&self) -> bool {
+        self.roche_limit < self.semi_major_axis && self.semi_major_axis < self.stable_orbit_limit
+    }
+}
+
+/// The region around a planet where its own gravity dominates over that of the star.
+/// Calculated at the closest approach to the star, where it is the smallest.
+pub fn calculate_hill_sphere_radius(planet_mass: EarthMass, planet_orbit: &Orbit) -> Kilometer {
+    // The mass of the star follows from the orbit of the planet, by Kepler's third law.
+    let star_mass = planet_orbit.semi_major_axis.cubed() / planet_orbit.period.squared();
+    let mass_ratio = planet_mass / EarthMass::from(star_mass);
+
+    Kilometer::from(planet_orbit.periapsis) * (mass_ratio / 3.).cbrt()
+}
+
+/// Time between two identical phases of a moon, seen from the planet.
+pub fn calculate_synodic_period(
+    sidereal_period: EarthDay,
+    planet_period: EarthDay,
+) -> Option<EarthDay> {
+    if sidereal_period < planet_period {
+        // The number of orbits per day of the moon, minus that of the planet.
+        let orbits_per_day = DAY / sidereal_period - DAY / planet_period;
+        Some(DAY / orbits_per_day)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::moon::*;
+    use crate::planet::PlanetComposition;
+    use crate::MainSequenceStar;
+
+    #[test]
+    fn test_our_moon() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let earth =
+            TerrestrialPlanet::calculate_parameters(1.0.into(), PlanetComposition::earth_like());
+        let earth_orbit = Orbit::calculate_parameters(&sun, 1.0.into(), 0.0167, 0.0.into());
+        let moon = TerrestrialPlanet::calculate_parameters(
+            0.0123.into(),
+            PlanetComposition::new(0.05, 0.95, 0.),
+        );
+
+        let moon =
+            Moon::calculate_parameters(&moon, MOON_DISTANCE, &MoonHost::from(&earth), &earth_orbit);
+
+        assert_eq!(27.3217, f32::from(moon.sidereal_period));
+        assert_eq!(29.530636, f32::from(moon.synodic_period.unwrap()));
+        assert_eq!(1.002585, moon.tidal_force);
+        assert_eq!(0.54372036, f32::from(moon.angular_size));
+        assert!(moon.is_orbit_stable());
+    }
+}
@@ -0,0 +1,131 @@
+use crate::units::*;
+use crate::MainSequenceStar;
+use serde::{Deserialize, Serialize};
+
+/// Call [Orbit::calculate_parameters] to get the values of an orbit around a star.
+/// The mass of the orbiting body is neglected, which is fine for planets.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct Orbit {
+    pub semi_major_axis: AstronomicalUnit,
+    /// 0 is a perfect circle, values approaching 1 are very elongated ellipses.
+    pub eccentricity: f32,
+    /// Relative to the reference plane of the system.
+    pub inclination: Degree,
+    pub period: EarthYear,
+    pub period_days: EarthDay,
+    /// Closest distance to the star.
+    pub periapsis: AstronomicalUnit,
+    /// Furthest distance from the star.
+    pub apoapsis: AstronomicalUnit,
+    /// Velocity of a circular orbit with the same semi-major axis.
+    pub mean_velocity: KilometerPerSecond,
+    pub periapsis_velocity: KilometerPerSecond,
+    pub apoapsis_velocity: KilometerPerSecond,
+    /// Averaged over the whole orbit.
+    pub insolation: EarthInsolation,
+}
+
+impl Orbit {
+    /// Eccentricity is clamped between 0 (inclusive) and 1 (exclusive), because
+    /// anything else is not a closed orbit.
+    pub fn calculate_parameters(
+        star: &MainSequenceStar,
+        semi_major_axis: AstronomicalUnit,
+        eccentricity: f32,
+        inclination: Degree,
+    ) -> Orbit {
+        let eccentricity = eccentricity.clamp(0., 0.999);
+
+        let period = calculate_orbital_period(semi_major_axis, star.current_mass);
+        let periapsis = semi_major_axis * (1. - eccentricity);
+        let apoapsis = semi_major_axis * (1. + eccentricity);
+
+        Orbit {
+            semi_major_axis,
+            eccentricity,
+            inclination,
+            period,
+            period_days: period.into(),
+            periapsis,
+            apoapsis,
+            mean_velocity: calculate_orbital_velocity(
+                semi_major_axis,
+                semi_major_axis,
+                star.current_mass,
+            ),
+            periapsis_velocity: calculate_orbital_velocity(
+                periapsis,
+                semi_major_axis,
+                star.current_mass,
+            ),
+            apoapsis_velocity: calculate_orbital_velocity(
+                apoapsis,
+                semi_major_axis,
+                star.current_mass,
+            ),
+            insolation: calculate_mean_insolation(star.luminosity, semi_major_axis, eccentricity),
+        }
+    }
+
+    /// Where the body is after the given fraction of its period has passed since periapsis.
+    /// Returns the true anomaly (the angle from periapsis, in radians) and the distance to the star.
+    pub fn position_at(&self, period_fraction: f32) -> (f32, AstronomicalUnit) {
+        let e = self.eccentricity;
+        let mean_anomaly = 2. * std::f32::consts::PI * period_fraction.rem_euclid(1.);
+        let eccentric_anomaly = calculate_eccentric_anomaly(mean_anomaly, e);
+
+        let true_anomaly = 2.
+            * ((1. + e).sqrt() * (eccentric_anomaly / 2.).sin())
+                .atan2((1. - e).sqrt() * (eccentric_anomaly / 2.).cos());
+        let distance = self.semi_major_axis * (1. - e * eccentric_anomaly.cos());
+
+        (true_anomaly, distance)
+    }
+}
+
+/// Solves Kepler's equation, M = E - e sin(E), for the eccentric anomaly E,
+/// using Newton's method. Angles are in radians.
+pub fn calculate_eccentric_anomaly(mean_anomaly: f32, eccentricity: f32) -> f32 {
+    // Starting at pi converges for all eccentricities, starting at M is faster for low ones.
+    let mut eccentric_anomaly = if eccentricity > 0.8 {
+        std::f32::consts::PI
+    } else {
+        mean_anomaly
+    };
+
+    for _ in 0..30 {
+        let error = eccentric_anomaly - eccentricity * eccentric_anomaly.sin() - mean_anomaly;
+        if error.abs() < 1e-6 {
+            break;
+        }
+        eccentric_anomaly -= error / (1. - eccentricity * eccentric_anomaly.cos());
+    }
+
+    eccentric_anomaly
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::orbit::*;
+
+    #[test]
+    fn test_earth_orbit() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let orbit = Orbit::calculate_parameters(&sun, 1.0.into(), 0.0167, 0.0.into());
+
+        assert_eq!(1., f32::from(orbit.period));
+        assert_eq!(0.9833, f32::from(orbit.periapsis));
+        assert_eq!(29.78, f32::from(orbit.mean_velocity));
+        assert_eq!(1.0001395, f32::from(orbit.insolation));
+    }
+
+    #[test]
+    fn test_position_in_eccentric_orbit() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let orbit = Orbit::calculate_parameters(&sun, 1.0.into(), 0.5, 0.0.into());
+
+        assert_eq!(0.5, f32::from(orbit.position_at(0.).1));
+        assert_eq!(1.5, f32::from(orbit.position_at(0.5).1));
+        assert_eq!(2.446561, orbit.position_at(0.25).0);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::units::*;
+use serde::{Deserialize, Serialize};
+
+/// Call [TerrestrialPlanet::calculate_parameters] to get the values of a semi realistic rocky planet.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TerrestrialPlanet {
+    pub mass: EarthMass,
+    pub composition: PlanetComposition,
+    pub radius: EarthRadius,
+    pub density: EarthDensity,
+    pub surface_gravity: Gravity,
+    pub escape_velocity: KilometerPerSecond,
+    pub surface_area: SquareKilometer,
+}
+
+impl TerrestrialPlanet {
+    pub fn calculate_parameters(
+        mass: EarthMass,
+        composition: PlanetComposition,
+    ) -> TerrestrialPlanet {
+        let radius = calculate_planet_radius(mass, &composition);
+        let density = EarthDensity::calculate(mass, radius);
+        let surface_gravity = Gravity::calculate(mass, radius);
+        let escape_velocity = calculate_escape_velocity(mass, radius);
+        let surface_area = calculate_surface_area(radius);
+
+        TerrestrialPlanet {
+            mass,
+            composition,
+            radius,
+            density,
+            surface_gravity,
+            escape_velocity,
+            surface_area,
+        }
+    }
+}
+
+/// Bulk composition of a planet, as mass fractions of an iron core,
+/// a silicate mantle and a water (ice) layer.
+/// The fractions always add up to 1.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PlanetComposition {
+    iron: f32,
+    silicate: f32,
+    water: f32,
+}
+
+impl PlanetComposition {
+    /// The fractions are normalized, so they don't have to add up to 1.
+    /// If all fractions are 0 (or negative), the composition of the earth is used instead.
+    pub fn new(iron: f32, silicate: f32, water: f32) -> Self {
+        let iron = iron.max(0.);
+        let silicate = silicate.max(0.);
+        let water = water.max(0.);
+
+        let total = iron + silicate + water;
+        if total <= 0. {
+            return Self::earth_like();
+        }
+
+        PlanetComposition {
+            iron: iron / total,
+            silicate: silicate / total,
+            water: water / total,
+        }
+    }
+
+    /// Roughly the composition of the earth: one third iron core, two thirds silicate mantle.
+    pub fn earth_like() -> Self {
+        PlanetComposition {
+            iron: 0.325,
+            silicate: 0.675,
+            water: 0.,
+        }
+    }
+
+    pub fn iron(&self) -> f32 {
+        self.iron
+    }
+
+    pub fn silicate(&self) -> f32 {
+        self.silicate
+    }
+
+    pub fn water(&self) -> f32 {
+        self.water
+    }
+
+    /// Fraction of the rocky (non-water) part of the planet that is silicate.
+    fn rock_fraction_of_rocky_part(&self) -> f32 {
+        let rocky = self.iron + self.silicate;
+        if rocky <= 0. {
+            0.
+        } else {
+            self.silicate / rocky
+        }
+    }
+}
+
+/// Uses the mass-radius relations of
+/// [Fortney et al. 2007](https://arxiv.org/abs/astro-ph/0612671).
+/// The rock/iron relation gives the radius of the dry planet,
+/// and the ice/rock relation is used to add the extra radius caused by the water layer.
+///
+/// These relations are fitted to planets between roughly 0.01 and 100 earth masses.
+pub fn calculate_planet_radius(mass: EarthMass, composition: &PlanetComposition) -> EarthRadius {
+    let log_mass = f32::from(mass).log10();
+
+    let rock_fraction = composition.rock_fraction_of_rocky_part();
+    let dry_radius = (0.0592 * rock_fraction + 0.0975) * log_mass.powi(2)
+        + (0.2337 * rock_fraction + 0.4938) * log_mass
+        + (0.3102 * rock_fraction + 0.7932);
+
+    let ice_rock_radius = |ice_fraction: f32| {
+        (0.0912 * ice_fraction + 0.1603) * log_mass.powi(2)
+            + (0.3330 * ice_fraction + 0.7387) * log_mass
+            + (0.4639 * ice_fraction + 1.1193)
+    };
+    let water_layer_radius = ice_rock_radius(composition.water) - ice_rock_radius(0.);
+
+    EarthRadius::from(dry_radius + water_layer_radius)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::planet::*;
+
+    #[test]
+    fn test_earth_like_planet() {
+        let earth =
+            TerrestrialPlanet::calculate_parameters(1.0.into(), PlanetComposition::earth_like());
+
+        assert_eq!(1.002585, f32::from(earth.radius));
+        assert_eq!(0.99484986, f32::from(earth.surface_gravity));
+    }
+
+    #[test]
+    fn test_composition_is_normalized() {
+        let composition = PlanetComposition::new(1., 2., 1.);
+
+        assert_eq!(0.25, composition.iron());
+        assert_eq!(0.5, composition.silicate());
+        assert_eq!(0.25, composition.water());
+    }
+}
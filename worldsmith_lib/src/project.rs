@@ -0,0 +1,175 @@
+use crate::system::StarSystem;
+use crate::units::SOLAR_LIFETIME_FRACTION;
+use crate::MainSequenceStar;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Version of the project file format written by this version of the application.
+///
+/// Increase this when the model changes in a way that older files can no longer be read as-is,
+/// and add a migration to [Project::from_ron].
+/// Fields that are simply added to the model should get `#[serde(default)]` instead,
+/// so that older files keep loading without a version change.
+///
+/// Version history:
+/// 1. First version.
+/// 2. Stars store their age.
+pub const PROJECT_VERSION: u32 = 2;
+
+/// Everything that belongs to one worldbuilding project. This is what gets saved to disk.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Project {
+    pub version: u32,
+    pub system: StarSystem,
+    /// Stars saved in the star calculator.
+    #[serde(default)]
+    pub saved_stars: Vec<MainSequenceStar>,
+}
+
+impl Project {
+    pub fn new(system: StarSystem, saved_stars: Vec<MainSequenceStar>) -> Self {
+        Project {
+            version: PROJECT_VERSION,
+            system,
+            saved_stars,
+        }
+    }
+
+    pub fn to_ron(&self) -> Result<String, ProjectError> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::new())
+            .map_err(ProjectError::Format)
+    }
+
+    /// The derived values (radius, period, etc.) that are stored in the file are recalculated
+    /// after loading, so that changes to the calculations are applied to older files as well.
+    pub fn from_ron(text: &str) -> Result<Self, ProjectError> {
+        let version = ron::from_str::<ProjectVersion>(text)
+            .map_err(ProjectError::Format)?
+            .version;
+
+        if version > PROJECT_VERSION {
+            return Err(ProjectError::UnsupportedVersion(version));
+        }
+
+        let mut project: Project = ron::from_str(text).map_err(ProjectError::Format)?;
+
+        if project.system.stars().is_empty() {
+            return Err(ProjectError::NoStars);
+        }
+
+        if version < 2 {
+            // Stars did not have an age yet, and their values matched those of a star
+            // at the same point in its life as the sun.
+            let mut set_age = |star: &mut MainSequenceStar| {
+                star.age_gigayears = star.max_age_gigayears * SOLAR_LIFETIME_FRACTION;
+            };
+
+            project.saved_stars.iter_mut().for_each(&mut set_age);
+            for index in 0..project.system.stars().len() {
+                if let Some(system_star) = project.system.star_mut(index) {
+                    set_age(&mut system_star.star);
+                }
+            }
+        }
+
+        project.version = PROJECT_VERSION;
+        project.system.recalculate();
+        for star in project.saved_stars.iter_mut() {
+            *star = MainSequenceStar::calculate_parameters(star.mass, star.age_gigayears);
+        }
+
+        Ok(project)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ProjectError> {
+        std::fs::write(path, self.to_ron()?).map_err(ProjectError::Io)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ProjectError> {
+        Self::from_ron(&std::fs::read_to_string(path).map_err(ProjectError::Io)?)
+    }
+}
+
+/// Only reads the version of a project file, so that it can be checked before
+/// trying to read the rest of the file.
+#[derive(Deserialize)]
+struct ProjectVersion {
+    version: u32,
+}
+
+#[derive(Debug)]
+pub enum ProjectError {
+    Io(std::io::Error),
+    Format(ron::Error),
+    /// The file was written by a newer version of the application.
+    UnsupportedVersion(u32),
+    /// Every star system should have at least one star.
+    NoStars,
+}
+
+impl Display for ProjectError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProjectError::Io(error) => write!(f, "Could not access the file: {}", error),
+            ProjectError::Format(error) => write!(f, "Not a valid project file: {}", error),
+            ProjectError::UnsupportedVersion(version) => write!(
+                f,
+                "The file has version {}, but only versions up to {} are supported. It was probably made with a newer version of the application",
+                version, PROJECT_VERSION
+            ),
+            ProjectError::NoStars => write!(f, "The star system in the file has no stars"),
+        }
+    }
+}
+
+impl std::error::Error for ProjectError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::project::*;
+    use crate::system::SystemStar;
+
+    fn project() -> Project {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        Project::new(
+            StarSystem::new("Sol", SystemStar::new("Sun", sun.clone())),
+            vec![sun],
+        )
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let text = project().to_ron().unwrap();
+        let loaded = Project::from_ron(&text).unwrap();
+
+        assert_eq!("Sol", loaded.system.name);
+        assert_eq!("Sun", loaded.system.primary_star().name);
+        assert_eq!(1, loaded.saved_stars.len());
+    }
+
+    #[test]
+    fn test_stars_without_age_are_migrated() {
+        let mut project = project();
+        project.version = 1;
+        project.saved_stars[0].age_gigayears = 0.0.into();
+        let text = project.to_ron().unwrap();
+
+        let loaded = Project::from_ron(&text).unwrap();
+
+        assert_eq!(PROJECT_VERSION, loaded.version);
+        assert_eq!(4.6, f32::from(loaded.saved_stars[0].age_gigayears));
+    }
+
+    #[test]
+    fn test_newer_version_is_rejected() {
+        let mut project = project();
+        project.version = PROJECT_VERSION + 1;
+        let text = project.to_ron().unwrap();
+
+        assert!(matches!(
+            Project::from_ron(&text),
+            Err(ProjectError::UnsupportedVersion(_))
+        ));
+    }
+}
@@ -0,0 +1,144 @@
+//! Seasons, caused by the tilt of the rotation axis of a planet, and by the changing distance
+//! to the star on an eccentric orbit.
+
+use crate::orbit::Orbit;
+use crate::units::*;
+use crate::MainSequenceStar;
+use std::f32::consts::PI;
+
+/// Where the star appears along the ecliptic at periapsis, measured from the northern spring
+/// equinox. The earth is closest to the sun in early january, just after the northern
+/// winter solstice. The same is assumed for other planets.
+const PERIAPSIS_SOLAR_LONGITUDE: Degree = Degree::new(283.);
+const RIGHT_ANGLE: Degree = Degree::new(90.);
+const STRAIGHT_ANGLE: Degree = Degree::new(180.);
+
+/// Call [Seasons::calculate_parameters] to get the seasons of a planet.
+///
+/// The time of year is given as a fraction of the year, where 0 is at periapsis.
+/// Latitudes are positive on the northern hemisphere, and negative on the southern one.
+#[derive(Clone, PartialEq)]
+pub struct Seasons {
+    /// Angle between the rotation axis and the orbit. The earth is tilted 23.4°.
+    pub axial_tilt: Degree,
+    /// Between the tropics, the star is directly overhead at noon at least once a year.
+    pub tropic_latitude: Degree,
+    /// Beyond the polar circles, the star doesn't set for at least one day in summer
+    /// (polar day), and doesn't rise for at least one day in winter (polar night).
+    pub polar_circle_latitude: Degree,
+    luminosity: SolarLuminosity,
+    orbit: Orbit,
+}
+
+impl Seasons {
+    /// Axial tilts beyond 90° mean that the planet rotates backwards, like venus and uranus.
+    pub fn calculate_parameters(
+        star: &MainSequenceStar,
+        orbit: &Orbit,
+        axial_tilt: Degree,
+    ) -> Seasons {
+        let tropic_latitude = axial_tilt
+            .min(STRAIGHT_ANGLE - axial_tilt)
+            .max(Degree::new(0.))
+            .min(RIGHT_ANGLE);
+
+        Seasons {
+            axial_tilt,
+            tropic_latitude,
+            polar_circle_latitude: RIGHT_ANGLE - tropic_latitude,
+            luminosity: star.luminosity,
+            orbit: orbit.clone(),
+        }
+    }
+
+    /// The latitude where the star is directly overhead at noon.
+    pub fn solar_declination(&self, year_fraction: f32) -> Degree {
+        let (true_anomaly, _) = self.orbit.position_at(year_fraction);
+        let solar_longitude = true_anomaly + PERIAPSIS_SOLAR_LONGITUDE.to_radians();
+
+        let tilt = self.axial_tilt.to_radians();
+        Degree::from_radians((tilt.sin() * solar_longitude.sin()).asin())
+    }
+
+    /// Light received at the top of the atmosphere, averaged over a whole day.
+    /// Relative to the light that the earth receives on a surface facing the sun.
+    pub fn insolation(&self, latitude: Degree, year_fraction: f32) -> EarthInsolation {
+        let (_, distance) = self.orbit.position_at(year_fraction);
+        let latitude = latitude.to_radians();
+        let declination = self.solar_declination(year_fraction).to_radians();
+        let hour_angle = calculate_sunset_hour_angle(latitude, declination);
+
+        let full_sun = self.luminosity / distance.squared();
+        let insolation = full_sun / PI
+            * (hour_angle * latitude.sin() * declination.sin()
+                + latitude.cos() * declination.cos() * hour_angle.sin());
+
+        // Rounding errors can make this slightly negative during polar night.
+        insolation.max(EarthInsolation::from(0.))
+    }
+
+    /// Fraction of the day that the star is above the horizon.
+    /// 1 is polar day, 0 is polar night.
+    pub fn daylight_fraction(&self, latitude: Degree, year_fraction: f32) -> f32 {
+        let declination = self.solar_declination(year_fraction).to_radians();
+        calculate_sunset_hour_angle(latitude.to_radians(), declination) / PI
+    }
+
+    /// Beyond this latitude it is polar day, and beyond the same latitude on the other
+    /// hemisphere it is polar night. Positive when it is summer in the north.
+    pub fn polar_day_latitude(&self, year_fraction: f32) -> Degree {
+        let declination = self.solar_declination(year_fraction);
+        if declination < Degree::new(0.) {
+            Degree::new(-90.) - declination
+        } else {
+            RIGHT_ANGLE - declination
+        }
+    }
+}
+
+/// Angle that the planet rotates between noon and sunset, in radians.
+/// Between 0 (the star doesn't rise) and pi (the star doesn't set).
+fn calculate_sunset_hour_angle(latitude: f32, declination: f32) -> f32 {
+    let height = latitude.sin() * declination.sin();
+    let spread = latitude.cos() * declination.cos();
+
+    if spread.abs() < 1e-6 {
+        // At the poles, the star stays at the same height all day.
+        match height {
+            height if height > 0. => PI,
+            height if height < 0. => 0.,
+            _ => PI / 2.,
+        }
+    } else {
+        (-height / spread).clamp(-1., 1.).acos()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::seasons::*;
+
+    #[test]
+    fn test_earth_seasons() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let orbit = Orbit::calculate_parameters(&sun, 1.0.into(), 0.0167, 0.0.into());
+        let seasons = Seasons::calculate_parameters(&sun, &orbit, 23.44.into());
+        // Around the 21st of june.
+        let northern_summer = 0.47;
+
+        assert_eq!(66.56, f32::from(seasons.polar_circle_latitude));
+        assert_eq!(
+            23.415379,
+            f32::from(seasons.solar_declination(northern_summer))
+        );
+        assert_eq!(1., seasons.daylight_fraction(80.0.into(), northern_summer));
+        assert_eq!(
+            0.,
+            seasons.daylight_fraction((-80.0).into(), northern_summer)
+        );
+        assert_eq!(
+            0.384663,
+            f32::from(seasons.insolation(90.0.into(), northern_summer))
+        );
+    }
+}
@@ -0,0 +1,546 @@
+use crate::binary::{BinaryComponent, BinaryOrbit};
+use crate::giant::GiantPlanet;
+use crate::moon::{Moon, MoonHost};
+use crate::orbit::Orbit;
+use crate::planet::TerrestrialPlanet;
+use crate::units::*;
+use crate::MainSequenceStar;
+use serde::{Deserialize, Serialize};
+
+/// A whole star system, with all of its stars, planets, giants, moons and belts.
+/// This is the document that a worldbuilding project is made of.
+///
+/// A system always has at least one star. Planets, giants and belts orbit one of these stars,
+/// referenced by index into [StarSystem::stars].
+/// Stars can orbit each other in binaries, see [StarSystem::binaries].
+#[derive(Clone, Serialize, Deserialize)]
+pub struct StarSystem {
+    pub name: String,
+    pub notes: String,
+    stars: Vec<SystemStar>,
+    /// Stars that orbit each other. Stars that are not part of a binary are
+    /// too far away from each other to influence their planets.
+    #[serde(default)]
+    binaries: Vec<SystemBinary>,
+    pub planets: Vec<SystemPlanet>,
+    /// Gas giants and ice giants.
+    #[serde(default)]
+    pub giants: Vec<SystemGiant>,
+    pub belts: Vec<AsteroidBelt>,
+}
+
+impl StarSystem {
+    pub fn new(name: impl Into<String>, primary_star: SystemStar) -> Self {
+        StarSystem {
+            name: name.into(),
+            notes: String::new(),
+            stars: vec![primary_star],
+            binaries: vec![],
+            planets: vec![],
+            giants: vec![],
+            belts: vec![],
+        }
+    }
+
+    pub fn stars(&self) -> &[SystemStar] {
+        &self.stars
+    }
+
+    /// The star with index 0. Every system has one.
+    pub fn primary_star(&self) -> &SystemStar {
+        &self.stars[0]
+    }
+
+    /// Returns the star with the given index, or the primary star if there is no such star.
+    pub fn star(&self, index: usize) -> &SystemStar {
+        self.stars.get(index).unwrap_or_else(|| self.primary_star())
+    }
+
+    pub fn add_star(&mut self, star: SystemStar) {
+        self.stars.push(star);
+    }
+
+    /// Only the name and notes can be changed directly,
+    /// because changing the star itself influences the orbits around it.
+    /// Use [StarSystem::replace_star] for that.
+    pub fn star_mut(&mut self, index: usize) -> Option<&mut SystemStar> {
+        self.stars.get_mut(index)
+    }
+
+    /// Replaces the parameters of the star with the given index,
+    /// and recalculates the orbits of everything that orbits it.
+    pub fn replace_star(&mut self, index: usize, star: MainSequenceStar) {
+        if let Some(system_star) = self.stars.get_mut(index) {
+            system_star.star = star;
+            self.recalculate_orbits();
+        }
+    }
+
+    /// Removes the star with the given index. The primary star can not be removed.
+    /// Everything that orbited the removed star will orbit the primary star instead.
+    pub fn remove_star(&mut self, index: usize) {
+        if index == 0 || index >= self.stars.len() {
+            return;
+        }
+
+        self.stars.remove(index);
+
+        self.rebuild_binaries(|star| match star {
+            star if star == index => None,
+            star if star > index => Some(star - 1),
+            star => Some(star),
+        });
+
+        let rehost = |host_star: &mut usize| {
+            if *host_star == index {
+                *host_star = 0;
+            } else if *host_star > index {
+                *host_star -= 1;
+            }
+        };
+
+        for planet in self.planets.iter_mut() {
+            rehost(&mut planet.host_star);
+        }
+        for giant in self.giants.iter_mut() {
+            rehost(&mut giant.host_star);
+        }
+        for belt in self.belts.iter_mut() {
+            rehost(&mut belt.host_star);
+        }
+
+        self.recalculate_orbits();
+    }
+
+    pub fn binaries(&self) -> &[SystemBinary] {
+        &self.binaries
+    }
+
+    /// Adds a binary of the given members. Nothing is added if one of the members does not exist,
+    /// or if both members are the same.
+    pub fn add_binary(
+        &mut self,
+        name: impl Into<String>,
+        primary: BinaryMember,
+        secondary: BinaryMember,
+        semi_major_axis: AstronomicalUnit,
+        eccentricity: f32,
+    ) {
+        let exists = |member| match member {
+            BinaryMember::Star(index) => index < self.stars.len(),
+            BinaryMember::Binary(index) => index < self.binaries.len(),
+        };
+        if primary == secondary || !exists(primary) || !exists(secondary) {
+            return;
+        }
+
+        let orbit = BinaryOrbit::calculate_parameters(
+            &self.binary_component(primary),
+            &self.binary_component(secondary),
+            semi_major_axis,
+            eccentricity,
+        );
+        self.binaries.push(SystemBinary {
+            name: name.into(),
+            notes: String::new(),
+            primary,
+            secondary,
+            orbit,
+        });
+    }
+
+    /// Changes to the members or orbit of the binary are applied by
+    /// [StarSystem::recalculate_orbits]. Binaries with members that don't exist are removed then.
+    pub fn binary_mut(&mut self, index: usize) -> Option<&mut SystemBinary> {
+        self.binaries.get_mut(index)
+    }
+
+    /// Binaries that have the removed binary as a member are removed as well.
+    pub fn remove_binary(&mut self, index: usize) {
+        if index < self.binaries.len() {
+            // A binary that refers to itself is removed when the binaries are rebuilt.
+            self.binaries[index].primary = BinaryMember::Binary(index);
+            self.recalculate_orbits();
+        }
+    }
+
+    /// The combined mass and luminosity of a member of a binary.
+    /// Panics if the member does not exist.
+    pub fn binary_component(&self, member: BinaryMember) -> BinaryComponent {
+        calculate_binary_component(&self.stars, &self.binaries, member)
+    }
+
+    /// Planets orbiting the given star are only stable within this distance,
+    /// because of the gravity of the other stars in its binaries.
+    /// `None` if the star is not part of a binary.
+    pub fn stable_orbit_limit(&self, star: usize) -> Option<AstronomicalUnit> {
+        self.binaries
+            .iter()
+            .filter_map(|binary| {
+                if binary.primary == BinaryMember::Star(star) {
+                    Some(binary.orbit.primary_stable_orbit_limit)
+                } else if binary.secondary == BinaryMember::Star(star) {
+                    Some(binary.orbit.secondary_stable_orbit_limit)
+                } else {
+                    None
+                }
+            })
+            .reduce(|a, b| if b < a { b } else { a })
+    }
+
+    /// Adds a planet orbiting the given star.
+    pub fn add_planet(
+        &mut self,
+        name: impl Into<String>,
+        host_star: usize,
+        planet: TerrestrialPlanet,
+        orbit: &Orbit,
+        axial_tilt: Degree,
+    ) {
+        let host_star = host_star.min(self.stars.len() - 1);
+        let orbit = Orbit::calculate_parameters(
+            &self.stars[host_star].star,
+            orbit.semi_major_axis,
+            orbit.eccentricity,
+            orbit.inclination,
+        );
+
+        self.planets.push(SystemPlanet {
+            name: name.into(),
+            notes: String::new(),
+            host_star,
+            planet,
+            orbit,
+            axial_tilt,
+            moons: vec![],
+        });
+    }
+
+    /// Adds a giant orbiting the given star.
+    pub fn add_giant(
+        &mut self,
+        name: impl Into<String>,
+        host_star: usize,
+        giant: &GiantPlanet,
+        orbit: &Orbit,
+    ) {
+        let host_star = host_star.min(self.stars.len() - 1);
+        let star = &self.stars[host_star].star;
+
+        self.giants.push(SystemGiant {
+            name: name.into(),
+            notes: String::new(),
+            host_star,
+            planet: GiantPlanet::calculate_parameters(
+                giant.mass,
+                giant.rotation_period,
+                star,
+                orbit.semi_major_axis,
+            ),
+            orbit: Orbit::calculate_parameters(
+                star,
+                orbit.semi_major_axis,
+                orbit.eccentricity,
+                orbit.inclination,
+            ),
+            moons: vec![],
+        });
+    }
+
+    /// Recalculates every star from its mass and age, and then every orbit.
+    pub fn recalculate(&mut self) {
+        for system_star in self.stars.iter_mut() {
+            system_star.star = MainSequenceStar::calculate_parameters(
+                system_star.star.mass,
+                system_star.star.age_gigayears,
+            );
+        }
+
+        self.recalculate_orbits();
+    }
+
+    /// Recalculates the orbit of every binary and of every planet around its host star.
+    /// Call this after changing the host star or orbital parameters of a planet.
+    pub fn recalculate_orbits(&mut self) {
+        let star_count = self.stars.len();
+        self.rebuild_binaries(|star| (star < star_count).then_some(star));
+
+        for planet in self.planets.iter_mut() {
+            if planet.host_star >= self.stars.len() {
+                planet.host_star = 0;
+            }
+
+            let star = &self.stars[planet.host_star].star;
+            planet.orbit = Orbit::calculate_parameters(
+                star,
+                planet.orbit.semi_major_axis,
+                planet.orbit.eccentricity,
+                planet.orbit.inclination,
+            );
+            recalculate_moons(
+                &mut planet.moons,
+                &MoonHost::from(&planet.planet),
+                &planet.orbit,
+            );
+        }
+
+        for giant in self.giants.iter_mut() {
+            if giant.host_star >= self.stars.len() {
+                giant.host_star = 0;
+            }
+
+            let star = &self.stars[giant.host_star].star;
+            giant.orbit = Orbit::calculate_parameters(
+                star,
+                giant.orbit.semi_major_axis,
+                giant.orbit.eccentricity,
+                giant.orbit.inclination,
+            );
+            // The temperature of a giant depends on its distance to the star.
+            giant.planet = GiantPlanet::calculate_parameters(
+                giant.planet.mass,
+                giant.planet.rotation_period,
+                star,
+                giant.orbit.semi_major_axis,
+            );
+            recalculate_moons(
+                &mut giant.moons,
+                &MoonHost::from(&giant.planet),
+                &giant.orbit,
+            );
+        }
+
+        for belt in self.belts.iter_mut() {
+            if belt.host_star >= self.stars.len() {
+                belt.host_star = 0;
+            }
+        }
+    }
+
+    /// Recalculates every binary, with the stars they refer to mapped to their new index.
+    /// Binaries with a member that no longer exists are removed, as are binaries that refer
+    /// to a binary that comes after them, or to themselves.
+    fn rebuild_binaries(&mut self, star_index: impl Fn(usize) -> Option<usize>) {
+        let mut binaries: Vec<SystemBinary> = vec![];
+        // New index of each of the old binaries, if it still exists.
+        let mut binary_index: Vec<Option<usize>> = vec![];
+
+        for mut binary in std::mem::take(&mut self.binaries) {
+            let new_member = |member| match member {
+                BinaryMember::Star(index) => star_index(index).map(BinaryMember::Star),
+                BinaryMember::Binary(index) => binary_index
+                    .get(index)
+                    .copied()
+                    .flatten()
+                    .map(BinaryMember::Binary),
+            };
+
+            match (new_member(binary.primary), new_member(binary.secondary)) {
+                (Some(primary), Some(secondary)) if primary != secondary => {
+                    binary.primary = primary;
+                    binary.secondary = secondary;
+                    binary.orbit = BinaryOrbit::calculate_parameters(
+                        &calculate_binary_component(&self.stars, &binaries, primary),
+                        &calculate_binary_component(&self.stars, &binaries, secondary),
+                        binary.orbit.semi_major_axis,
+                        binary.orbit.eccentricity,
+                    );
+
+                    binary_index.push(Some(binaries.len()));
+                    binaries.push(binary);
+                }
+                _ => binary_index.push(None),
+            }
+        }
+
+        self.binaries = binaries;
+    }
+}
+
+fn recalculate_moons(moons: &mut [SystemMoon], host: &MoonHost, host_orbit: &Orbit) {
+    for moon in moons.iter_mut() {
+        moon.moon = Moon::calculate_parameters(&moon.body, moon.semi_major_axis, host, host_orbit);
+    }
+}
+
+/// Binaries only refer to binaries that come before them, so this always ends.
+fn calculate_binary_component(
+    stars: &[SystemStar],
+    binaries: &[SystemBinary],
+    member: BinaryMember,
+) -> BinaryComponent {
+    match member {
+        BinaryMember::Star(index) => BinaryComponent::from_star(&stars[index].star),
+        BinaryMember::Binary(index) => {
+            let binary = &binaries[index];
+            BinaryComponent::combine(
+                &calculate_binary_component(stars, binaries, binary.primary),
+                &calculate_binary_component(stars, binaries, binary.secondary),
+            )
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SystemStar {
+    pub name: String,
+    pub notes: String,
+    pub star: MainSequenceStar,
+}
+
+impl SystemStar {
+    pub fn new(name: impl Into<String>, star: MainSequenceStar) -> Self {
+        SystemStar {
+            name: name.into(),
+            notes: String::new(),
+            star,
+        }
+    }
+}
+
+/// One side of a [SystemBinary].
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum BinaryMember {
+    /// Index into [StarSystem::stars].
+    Star(usize),
+    /// Index into [StarSystem::binaries], for systems of three or more stars.
+    /// Should be lower than the index of the binary it is a member of.
+    Binary(usize),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SystemBinary {
+    pub name: String,
+    pub notes: String,
+    /// Call [StarSystem::recalculate_orbits] after changing this.
+    pub primary: BinaryMember,
+    /// Call [StarSystem::recalculate_orbits] after changing this.
+    pub secondary: BinaryMember,
+    /// Call [StarSystem::recalculate_orbits] after changing this.
+    pub orbit: BinaryOrbit,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SystemPlanet {
+    pub name: String,
+    pub notes: String,
+    /// Index of the star this planet orbits.
+    pub host_star: usize,
+    pub planet: TerrestrialPlanet,
+    /// Call [StarSystem::recalculate_orbits] after changing this.
+    pub orbit: Orbit,
+    /// Angle between the rotation axis and the orbit, see [crate::seasons::Seasons].
+    #[serde(default)]
+    pub axial_tilt: Degree,
+    pub moons: Vec<SystemMoon>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SystemGiant {
+    pub name: String,
+    pub notes: String,
+    /// Index of the star this giant orbits.
+    pub host_star: usize,
+    /// Call [StarSystem::recalculate_orbits] after changing this.
+    pub planet: GiantPlanet,
+    /// Call [StarSystem::recalculate_orbits] after changing this.
+    pub orbit: Orbit,
+    pub moons: Vec<SystemMoon>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SystemMoon {
+    pub name: String,
+    pub notes: String,
+    pub body: TerrestrialPlanet,
+    /// Average distance between the centers of the planet and the moon.
+    pub semi_major_axis: Kilometer,
+    /// Follows from the body and semi-major axis.
+    /// Call [StarSystem::recalculate_orbits] after changing those.
+    #[serde(default)]
+    pub moon: Moon,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AsteroidBelt {
+    pub name: String,
+    pub notes: String,
+    /// Index of the star this belt orbits.
+    pub host_star: usize,
+    pub inner_edge: AstronomicalUnit,
+    pub outer_edge: AstronomicalUnit,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::planet::PlanetComposition;
+    use crate::system::*;
+
+    fn sun() -> MainSequenceStar {
+        MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into())
+    }
+
+    fn earth_orbit() -> Orbit {
+        Orbit::calculate_parameters(&sun(), 1.0.into(), 0.0, 0.0.into())
+    }
+
+    #[test]
+    fn test_orbits_follow_their_host_star() {
+        let mut system = StarSystem::new("Test", SystemStar::new("A", sun()));
+        system.add_star(SystemStar::new(
+            "B",
+            MainSequenceStar::calculate_parameters(4.0.into(), 0.1.into()),
+        ));
+
+        let earth =
+            TerrestrialPlanet::calculate_parameters(1.0.into(), PlanetComposition::earth_like());
+        system.add_planet("Around A", 0, earth.clone(), &earth_orbit(), 23.44.into());
+        system.add_planet("Around B", 1, earth, &earth_orbit(), 23.44.into());
+
+        assert_eq!(1., f32::from(system.planets[0].orbit.period));
+        assert_eq!(0.5, f32::from(system.planets[1].orbit.period));
+
+        system.remove_star(1);
+
+        assert_eq!(0, system.planets[1].host_star);
+        assert_eq!(1., f32::from(system.planets[1].orbit.period));
+    }
+
+    #[test]
+    fn test_triple_star_system() {
+        let mut system = StarSystem::new("Test", SystemStar::new("A", sun()));
+        system.add_star(SystemStar::new("B", sun()));
+        system.add_star(SystemStar::new("C", sun()));
+
+        system.add_binary(
+            "AB",
+            BinaryMember::Star(0),
+            BinaryMember::Star(1),
+            1.0.into(),
+            0.,
+        );
+        system.add_binary(
+            "AB-C",
+            BinaryMember::Binary(0),
+            BinaryMember::Star(2),
+            100.0.into(),
+            0.,
+        );
+
+        assert_eq!(577.3503, f32::from(system.binaries()[1].orbit.period));
+        assert_eq!(0.274, f32::from(system.stable_orbit_limit(0).unwrap()));
+
+        // Without star B, there is no binary AB, and so also no binary AB-C.
+        system.remove_star(1);
+
+        assert!(system.binaries().is_empty());
+    }
+
+    #[test]
+    fn test_primary_star_can_not_be_removed() {
+        let mut system = StarSystem::new("Test", SystemStar::new("A", sun()));
+        system.remove_star(0);
+
+        assert_eq!(1, system.stars().len());
+    }
+}
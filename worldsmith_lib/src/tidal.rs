@@ -0,0 +1,204 @@
+//! Tidal locking: the tides raised by a star or planet slowly brake the rotation of a body
+//! orbiting it, until one side always faces the host. This is why we only ever see one side
+//! of our moon, and why planets in the habitable zone of red dwarfs are usually locked.
+//!
+//! The numbers involved are very large, so this is calculated in f64, in SI units.
+
+use crate::moon::MoonHost;
+use crate::orbit::Orbit;
+use crate::planet::TerrestrialPlanet;
+use crate::unit_system::{ConvertUnit, UnitSystem};
+use crate::units::*;
+use crate::MainSequenceStar;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Gravitational constant, in m³/(kg s²).
+const GRAVITATIONAL_CONSTANT: f64 = 6.674_30e-11;
+const SECONDS_PER_GIGAYEAR: f64 = 3.155_76e16;
+
+/// Rigidity of rocky bodies, in Pa.
+const ROCK_RIGIDITY: f64 = 3e10;
+/// Dissipation function. Lower means that more of the energy of the tides is lost as heat.
+const ROCK_QUALITY_FACTOR: f64 = 100.;
+/// Assumes a body of the same density throughout. Real planets are denser towards
+/// the center, but the uncertainty in the other values is much larger than this.
+const MOMENT_OF_INERTIA_FACTOR: f64 = 0.4;
+
+/// Newly formed planets rotate quickly. Use this when the initial rotation is not known.
+pub const TYPICAL_INITIAL_ROTATION_PERIOD_HOURS: f32 = 12.;
+
+/// Eccentric orbits can get caught in a spin-orbit resonance instead of being locked,
+/// like mercury, which rotates 3 times for every 2 orbits.
+const MIN_RESONANCE_ECCENTRICITY: f32 = 0.1;
+
+/// Call [TidalLocking::calculate_for_planet] or [TidalLocking::calculate_for_moon]
+/// to find out how a body rotates.
+#[derive(Copy, Clone)]
+pub struct TidalLocking {
+    /// Time it takes for the tides to slow the rotation down until the body is locked.
+    pub timescale_gigayears: Gigayear,
+    pub state: RotationState,
+}
+
+impl TidalLocking {
+    /// Locked to the star, which has been around for [MainSequenceStar::age_gigayears].
+    pub fn calculate_for_planet(
+        planet: &TerrestrialPlanet,
+        initial_rotation_period: Hour,
+        star: &MainSequenceStar,
+        orbit: &Orbit,
+    ) -> Self {
+        let timescale_gigayears = calculate_tidal_locking_timescale(
+            planet,
+            initial_rotation_period,
+            star.current_mass.convert(UnitSystem::Si),
+            orbit.semi_major_axis.convert(UnitSystem::Si),
+        );
+
+        TidalLocking {
+            timescale_gigayears,
+            state: RotationState::calculate(
+                timescale_gigayears,
+                star.age_gigayears,
+                orbit.eccentricity,
+            ),
+        }
+    }
+
+    /// Locked to the planet. Moons are assumed to be as old as the star.
+    pub fn calculate_for_moon(
+        moon: &TerrestrialPlanet,
+        initial_rotation_period: Hour,
+        host: &MoonHost,
+        semi_major_axis: Kilometer,
+        age_gigayears: Gigayear,
+    ) -> Self {
+        let timescale_gigayears = calculate_tidal_locking_timescale(
+            moon,
+            initial_rotation_period,
+            host.mass.convert(UnitSystem::Si),
+            semi_major_axis.convert(UnitSystem::Si),
+        );
+
+        TidalLocking {
+            timescale_gigayears,
+            // Moons are usually on nearly circular orbits.
+            state: RotationState::calculate(timescale_gigayears, age_gigayears, 0.),
+        }
+    }
+}
+
+/// Uses the approximation of [Gladman et al. 1996](https://doi.org/10.1006/icar.1996.0117),
+/// for a rocky body. The result can easily be off by a factor of 10 either way, but that is
+/// still good enough to tell a locked planet from a freely rotating one.
+pub fn calculate_tidal_locking_timescale(
+    body: &TerrestrialPlanet,
+    initial_rotation_period: Hour,
+    host_mass_kg: f64,
+    distance_m: f64,
+) -> Gigayear {
+    let mass = body.mass.convert(UnitSystem::Si);
+    let radius = body.radius.convert(UnitSystem::Si);
+    let density = mass / (4. / 3. * std::f64::consts::PI * radius.powi(3));
+    let gravity = body.surface_gravity.convert(UnitSystem::Si);
+
+    let initial_spin_rate =
+        2. * std::f64::consts::PI / initial_rotation_period.convert(UnitSystem::Si);
+    let moment_of_inertia = MOMENT_OF_INERTIA_FACTOR * mass * radius.powi(2);
+    // Tidal Love number, how much the body deforms under the tides.
+    let love_number = 1.5 / (1. + 19. * ROCK_RIGIDITY / (2. * density * gravity * radius));
+
+    let seconds = initial_spin_rate * distance_m.powi(6) * moment_of_inertia * ROCK_QUALITY_FACTOR
+        / (3. * GRAVITATIONAL_CONSTANT * host_mass_kg.powi(2) * love_number * radius.powi(5));
+
+    Gigayear::new((seconds / SECONDS_PER_GIGAYEAR) as f32)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum RotationState {
+    /// One side always faces the host.
+    TidallyLocked,
+    /// Rotates `spin` times for every `orbits` orbits.
+    SpinOrbitResonance {
+        spin: u8,
+        orbits: u8,
+    },
+    FreelyRotating,
+}
+
+impl RotationState {
+    pub fn calculate(
+        timescale_gigayears: Gigayear,
+        age_gigayears: Gigayear,
+        eccentricity: f32,
+    ) -> Self {
+        if timescale_gigayears > age_gigayears {
+            RotationState::FreelyRotating
+        } else if eccentricity >= MIN_RESONANCE_ECCENTRICITY {
+            RotationState::SpinOrbitResonance { spin: 3, orbits: 2 }
+        } else {
+            RotationState::TidallyLocked
+        }
+    }
+}
+
+impl Display for RotationState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RotationState::TidallyLocked => write!(f, "tidally locked"),
+            RotationState::SpinOrbitResonance { spin, orbits } => {
+                write!(f, "{}:{} spin-orbit resonance", spin, orbits)
+            }
+            RotationState::FreelyRotating => write!(f, "freely rotating"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::planet::PlanetComposition;
+    use crate::tidal::*;
+
+    #[test]
+    fn test_earth_and_moon() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let earth =
+            TerrestrialPlanet::calculate_parameters(1.0.into(), PlanetComposition::earth_like());
+        let orbit = Orbit::calculate_parameters(&sun, 1.0.into(), 0.0167, 0.0.into());
+        let moon = TerrestrialPlanet::calculate_parameters(
+            0.0123.into(),
+            PlanetComposition::new(0.05, 0.95, 0.),
+        );
+
+        let earth_locking = TidalLocking::calculate_for_planet(&earth, 12.0.into(), &sun, &orbit);
+        let moon_locking = TidalLocking::calculate_for_moon(
+            &moon,
+            12.0.into(),
+            &MoonHost::from(&earth),
+            384_400.0.into(),
+            4.6.into(),
+        );
+
+        assert_eq!(RotationState::FreelyRotating, earth_locking.state);
+        assert_eq!(RotationState::TidallyLocked, moon_locking.state);
+        assert_eq!(0.02498915, f32::from(moon_locking.timescale_gigayears));
+    }
+
+    #[test]
+    fn test_mercury_is_in_resonance() {
+        let sun = MainSequenceStar::calculate_parameters(1.0.into(), 4.6.into());
+        let mercury = TerrestrialPlanet::calculate_parameters(
+            0.055.into(),
+            PlanetComposition::new(0.7, 0.3, 0.),
+        );
+        let orbit = Orbit::calculate_parameters(&sun, 0.387.into(), 0.2056, 7.0.into());
+
+        let locking = TidalLocking::calculate_for_planet(&mercury, 12.0.into(), &sun, &orbit);
+
+        assert_eq!(
+            RotationState::SpinOrbitResonance { spin: 3, orbits: 2 },
+            locking.state
+        );
+    }
+}
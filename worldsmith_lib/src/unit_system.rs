@@ -0,0 +1,406 @@
+//! Showing values in other units than the ones they are calculated in.
+//!
+//! Values are always calculated and stored in the units of [crate::units]. Use
+//! [ConvertUnit::convert] to get a value in the unit system that the user prefers,
+//! or in [UnitSystem::Si] for formulas that need the actual physical constants.
+
+use crate::units::*;
+use std::fmt::{Display, Formatter};
+
+const SOLAR_MASS_KG: f64 = 1.98847e30;
+const SOLAR_RADIUS_M: f64 = 6.957e8;
+const SOLAR_LUMINOSITY_W: f64 = 3.828e26;
+/// Mean density of the sun.
+const SOLAR_DENSITY_KG_M3: f64 = 1408.;
+const EARTH_MASS_KG: f64 = 5.9722e24;
+const EARTH_RADIUS_M: f64 = 6.371e6;
+const EARTH_DENSITY_KG_M3: f64 = 5514.;
+/// Mean radius, the same as [JupiterRadius].
+const JUPITER_RADIUS_M: f64 = 6.9911e7;
+const ASTRONOMICAL_UNIT_M: f64 = 1.495978707e11;
+const POUND_KG: f64 = 0.45359237;
+const MILE_M: f64 = 1609.344;
+const POUND_PER_CUBIC_FOOT_KG_M3: f64 = 16.018463;
+const FOOT_M: f64 = 0.3048;
+/// Standard gravity, the same as [Gravity].
+const GRAVITY_M_S2: f64 = 9.80665;
+const BAR_PA: f64 = 1e5;
+const POUND_PER_SQUARE_INCH_PA: f64 = 6894.757;
+const ZERO_CELSIUS_K: f64 = 273.15;
+/// Absolute zero, in degrees fahrenheit.
+const ZERO_KELVIN_F: f64 = -459.67;
+
+/// The units that values are shown in.
+/// Quantities that have no unit of their own in a system are shown in the unit they are
+/// calculated in, so planets keep their earth units in the solar system.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum UnitSystem {
+    /// The units that everything is calculated in, relative to the sun.
+    #[default]
+    Solar,
+    /// Relative to the earth, with temperatures in degrees celsius.
+    Earth,
+    /// Relative to jupiter, for giants and brown dwarfs.
+    Jupiter,
+    /// Kilograms, meters and watts.
+    Si,
+    /// Pounds, miles and degrees fahrenheit.
+    Imperial,
+}
+
+impl UnitSystem {
+    pub const ALL: [UnitSystem; 5] = [
+        UnitSystem::Solar,
+        UnitSystem::Earth,
+        UnitSystem::Jupiter,
+        UnitSystem::Si,
+        UnitSystem::Imperial,
+    ];
+}
+
+impl Display for UnitSystem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnitSystem::Solar => write!(f, "Solar"),
+            UnitSystem::Earth => write!(f, "Earth"),
+            UnitSystem::Jupiter => write!(f, "Jupiter"),
+            UnitSystem::Si => write!(f, "SI"),
+            UnitSystem::Imperial => write!(f, "Imperial"),
+        }
+    }
+}
+
+/// A unit to show a value in: `value * factor + offset`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UnitConversion {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    factor: f64,
+    offset: f64,
+}
+
+impl UnitConversion {
+    /// Shows the value as it is stored.
+    pub fn unchanged<U: Unit>() -> Self {
+        UnitConversion::scale(U::SYMBOL, U::NAME, 1.)
+    }
+
+    fn scale(symbol: &'static str, name: &'static str, factor: f64) -> Self {
+        UnitConversion {
+            symbol,
+            name,
+            factor,
+            offset: 0.,
+        }
+    }
+
+    fn with_factor(self, factor: f64) -> Self {
+        UnitConversion { factor, ..self }
+    }
+
+    pub fn apply(&self, value: f32) -> f64 {
+        f64::from(value) * self.factor + self.offset
+    }
+}
+
+/// Units that can be shown in every [UnitSystem].
+pub trait ConvertUnit: Unit + Copy + Into<f32> {
+    /// The unit that this quantity is shown in, in the given system.
+    fn conversion(system: UnitSystem) -> UnitConversion;
+
+    fn convert(self, system: UnitSystem) -> f64 {
+        Self::conversion(system).apply(self.into())
+    }
+}
+
+impl ConvertUnit for SolarMass {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar => UnitConversion::unchanged::<Self>(),
+            UnitSystem::Earth => UnitConversion::unchanged::<EarthMass>().with_factor(
+                f64::from(EARTH_MASSES_PER_JUPITER_MASS) / f64::from(SOLAR_MASSES_PER_JUPITER_MASS),
+            ),
+            UnitSystem::Jupiter => UnitConversion::unchanged::<JupiterMass>()
+                .with_factor(1. / f64::from(SOLAR_MASSES_PER_JUPITER_MASS)),
+            UnitSystem::Si => UnitConversion::scale("kg", "kilogram", SOLAR_MASS_KG),
+            UnitSystem::Imperial => UnitConversion::scale("lb", "pound", SOLAR_MASS_KG / POUND_KG),
+        }
+    }
+}
+
+impl ConvertUnit for SolarRadius {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar => UnitConversion::unchanged::<Self>(),
+            UnitSystem::Earth => UnitConversion::unchanged::<EarthRadius>()
+                .with_factor(SOLAR_RADIUS_M / EARTH_RADIUS_M),
+            UnitSystem::Jupiter => UnitConversion::unchanged::<JupiterRadius>()
+                .with_factor(SOLAR_RADIUS_M / JUPITER_RADIUS_M),
+            UnitSystem::Si => UnitConversion::scale("m", "meter", SOLAR_RADIUS_M),
+            UnitSystem::Imperial => UnitConversion::scale("mi", "mile", SOLAR_RADIUS_M / MILE_M),
+        }
+    }
+}
+
+impl ConvertUnit for SolarLuminosity {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si | UnitSystem::Imperial => {
+                UnitConversion::scale("W", "watt", SOLAR_LUMINOSITY_W)
+            }
+        }
+    }
+}
+
+impl ConvertUnit for SolarDensity {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Jupiter => UnitConversion::unchanged::<Self>(),
+            UnitSystem::Earth => UnitConversion::unchanged::<EarthDensity>()
+                .with_factor(SOLAR_DENSITY_KG_M3 / EARTH_DENSITY_KG_M3),
+            UnitSystem::Si => {
+                UnitConversion::scale("kg/m³", "kilogram per cubic meter", SOLAR_DENSITY_KG_M3)
+            }
+            UnitSystem::Imperial => UnitConversion::scale(
+                "lb/ft³",
+                "pound per cubic foot",
+                SOLAR_DENSITY_KG_M3 / POUND_PER_CUBIC_FOOT_KG_M3,
+            ),
+        }
+    }
+}
+
+/// Distances between planets and stars stay in AU, except in [UnitSystem::Si]
+/// and [UnitSystem::Imperial].
+impl ConvertUnit for AstronomicalUnit {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si => UnitConversion::scale("m", "meter", ASTRONOMICAL_UNIT_M),
+            UnitSystem::Imperial => {
+                UnitConversion::scale("mi", "mile", ASTRONOMICAL_UNIT_M / MILE_M)
+            }
+        }
+    }
+}
+
+impl ConvertUnit for Kelvin {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Jupiter | UnitSystem::Si => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Earth => UnitConversion {
+                offset: -ZERO_CELSIUS_K,
+                ..UnitConversion::unchanged::<Celsius>()
+            },
+            UnitSystem::Imperial => UnitConversion {
+                symbol: "°F",
+                name: "degree fahrenheit",
+                factor: 9. / 5.,
+                offset: ZERO_KELVIN_F,
+            },
+        }
+    }
+}
+
+impl ConvertUnit for EarthMass {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth => UnitConversion::unchanged::<Self>(),
+            UnitSystem::Jupiter => UnitConversion::unchanged::<JupiterMass>()
+                .with_factor(1. / f64::from(EARTH_MASSES_PER_JUPITER_MASS)),
+            UnitSystem::Si => UnitConversion::scale("kg", "kilogram", EARTH_MASS_KG),
+            UnitSystem::Imperial => UnitConversion::scale("lb", "pound", EARTH_MASS_KG / POUND_KG),
+        }
+    }
+}
+
+impl ConvertUnit for JupiterMass {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        let jupiter_mass_kg = f64::from(EARTH_MASSES_PER_JUPITER_MASS) * EARTH_MASS_KG;
+        match system {
+            UnitSystem::Solar | UnitSystem::Jupiter => UnitConversion::unchanged::<Self>(),
+            UnitSystem::Earth => UnitConversion::unchanged::<EarthMass>()
+                .with_factor(f64::from(EARTH_MASSES_PER_JUPITER_MASS)),
+            UnitSystem::Si => UnitConversion::scale("kg", "kilogram", jupiter_mass_kg),
+            UnitSystem::Imperial => {
+                UnitConversion::scale("lb", "pound", jupiter_mass_kg / POUND_KG)
+            }
+        }
+    }
+}
+
+impl ConvertUnit for EarthRadius {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth => UnitConversion::unchanged::<Self>(),
+            UnitSystem::Jupiter => UnitConversion::unchanged::<JupiterRadius>()
+                .with_factor(EARTH_RADIUS_M / JUPITER_RADIUS_M),
+            UnitSystem::Si => UnitConversion::scale("m", "meter", EARTH_RADIUS_M),
+            UnitSystem::Imperial => UnitConversion::scale("mi", "mile", EARTH_RADIUS_M / MILE_M),
+        }
+    }
+}
+
+impl ConvertUnit for JupiterRadius {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Jupiter => UnitConversion::unchanged::<Self>(),
+            UnitSystem::Earth => UnitConversion::unchanged::<EarthRadius>()
+                .with_factor(JUPITER_RADIUS_M / EARTH_RADIUS_M),
+            UnitSystem::Si => UnitConversion::scale("m", "meter", JUPITER_RADIUS_M),
+            UnitSystem::Imperial => UnitConversion::scale("mi", "mile", JUPITER_RADIUS_M / MILE_M),
+        }
+    }
+}
+
+impl ConvertUnit for EarthDensity {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si => {
+                UnitConversion::scale("kg/m³", "kilogram per cubic meter", EARTH_DENSITY_KG_M3)
+            }
+            UnitSystem::Imperial => UnitConversion::scale(
+                "lb/ft³",
+                "pound per cubic foot",
+                EARTH_DENSITY_KG_M3 / POUND_PER_CUBIC_FOOT_KG_M3,
+            ),
+        }
+    }
+}
+
+impl ConvertUnit for Gravity {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si => {
+                UnitConversion::scale("m/s²", "meter per second squared", GRAVITY_M_S2)
+            }
+            UnitSystem::Imperial => {
+                UnitConversion::scale("ft/s²", "foot per second squared", GRAVITY_M_S2 / FOOT_M)
+            }
+        }
+    }
+}
+
+impl ConvertUnit for Kilometer {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si => UnitConversion::scale("m", "meter", 1000.),
+            UnitSystem::Imperial => UnitConversion::scale("mi", "mile", 1000. / MILE_M),
+        }
+    }
+}
+
+impl ConvertUnit for SquareKilometer {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si => UnitConversion::scale("m²", "square meter", 1e6),
+            UnitSystem::Imperial => {
+                UnitConversion::scale("mi²", "square mile", 1e6 / (MILE_M * MILE_M))
+            }
+        }
+    }
+}
+
+impl ConvertUnit for KilometerPerSecond {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si => UnitConversion::scale("m/s", "meter per second", 1000.),
+            UnitSystem::Imperial => {
+                UnitConversion::scale("mi/s", "mile per second", 1000. / MILE_M)
+            }
+        }
+    }
+}
+
+/// Periods of rotation stay in hours, except in [UnitSystem::Si].
+impl ConvertUnit for Hour {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter | UnitSystem::Imperial => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si => UnitConversion::scale("s", "second", 3600.),
+        }
+    }
+}
+
+impl ConvertUnit for Bar {
+    fn conversion(system: UnitSystem) -> UnitConversion {
+        match system {
+            UnitSystem::Solar | UnitSystem::Earth | UnitSystem::Jupiter => {
+                UnitConversion::unchanged::<Self>()
+            }
+            UnitSystem::Si => UnitConversion::scale("Pa", "pascal", BAR_PA),
+            UnitSystem::Imperial => UnitConversion::scale(
+                "psi",
+                "pound per square inch",
+                BAR_PA / POUND_PER_SQUARE_INCH_PA,
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unit_system::*;
+
+    #[test]
+    fn test_sun_in_other_units() {
+        assert_eq!(1.98847e30, SolarMass::new(1.).convert(UnitSystem::Si));
+        assert_eq!(
+            332945.7248658117,
+            SolarMass::new(1.).convert(UnitSystem::Earth)
+        );
+        assert_eq!(
+            109.1979281117564,
+            SolarRadius::from(1.).convert(UnitSystem::Earth)
+        );
+        assert_eq!(5502.85, Kelvin::from(5776.).convert(UnitSystem::Earth));
+        assert_eq!(
+            1.495978707e11,
+            AstronomicalUnit::new(1.).convert(UnitSystem::Si)
+        );
+        assert_eq!(
+            9937.130000000001,
+            Kelvin::from(5776.).convert(UnitSystem::Imperial)
+        );
+    }
+
+    #[test]
+    fn test_planets_in_other_units() {
+        assert_eq!(
+            0.09113015119223013,
+            EarthRadius::from(1.).convert(UnitSystem::Jupiter)
+        );
+        assert_eq!(
+            317.8299865722656,
+            JupiterMass::from(1.).convert(UnitSystem::Earth)
+        );
+        assert_eq!(
+            32.17404855643044,
+            Gravity::from(1.).convert(UnitSystem::Imperial)
+        );
+    }
+}
@@ -1,5 +1,7 @@
-use derive_more::{Display, From, Into, Sub};
+use derive_more::{Display, From, Into};
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
+use std::ops::{Add, Div, Mul, Sub};
 
 pub trait Unit {
     const SYMBOL: &'static str;
@@ -7,56 +9,117 @@ pub trait Unit {
 }
 
 /// M☉ = 1.98847 * 10^30 kg
-#[derive(Copy, Clone, Display, From, Into)]
+#[derive(
+    Copy, Clone, Default, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize,
+)]
+#[serde(transparent)]
 pub struct SolarMass(f32);
 
 impl SolarMass {
-    pub fn new(mass: f32) -> Self {
+    pub const fn new(mass: f32) -> Self {
         SolarMass(mass)
     }
 }
 
+impl From<JupiterMass> for SolarMass {
+    fn from(mass: JupiterMass) -> Self {
+        SolarMass(mass.0 * SOLAR_MASSES_PER_JUPITER_MASS)
+    }
+}
+
 impl Unit for SolarMass {
     const SYMBOL: &'static str = "M☉";
     const NAME: &'static str = "solar mass";
 }
 
-#[derive(Copy, Clone, Display, From, Into)]
+/// M♃ = 1.89813 * 10^27 kg
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JupiterMass(f32);
+
+impl JupiterMass {
+    pub const fn new(mass: f32) -> Self {
+        JupiterMass(mass)
+    }
+}
+
+impl From<SolarMass> for JupiterMass {
+    fn from(mass: SolarMass) -> Self {
+        JupiterMass(mass.0 / SOLAR_MASSES_PER_JUPITER_MASS)
+    }
+}
+
+impl Unit for JupiterMass {
+    const SYMBOL: &'static str = "M♃";
+    const NAME: &'static str = "jupiter mass";
+}
+
+/// R♃ = 69911 km, the mean radius of jupiter.
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JupiterRadius(f32);
+
+impl Unit for JupiterRadius {
+    const SYMBOL: &'static str = "R♃";
+    const NAME: &'static str = "jupiter radius";
+}
+
+#[derive(Copy, Clone, Display, From, Into, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct SolarRadius(f32);
 
+impl SolarRadius {
+    pub const fn new(radius: f32) -> Self {
+        SolarRadius(radius)
+    }
+}
+
 impl SolarRadius {
     /// In actuality, the radius of larger main sequence stars is also said to be dependent
     /// on age and composition, but this is close enough for now.
     ///
-    /// TODO (Wybe 2022-07-03): Make dependent on age as well?
+    /// Radius of a star of this mass at the same point in its life as the sun is now.
+    /// Use [SolarRadius::calculate_from_luminosity] for the radius at other ages.
+    ///
     /// TODO (Wybe 2022-07-03): Make the magic numbers no longer magic.
     pub fn calculate(mass: SolarMass) -> Self {
-        let mass = mass.0;
+        let mass = mass / SUN_MASS;
         if mass < 1. {
-            Self(mass.powf(0.8))
+            SUN_RADIUS * mass.powf(0.8)
         } else {
-            Self(mass.powf(0.57))
+            SUN_RADIUS * mass.powf(0.57)
         }
     }
 }
 
+impl SolarRadius {
+    /// Radius of a black body with the given luminosity and surface temperature.
+    pub fn calculate_from_luminosity(luminosity: SolarLuminosity, temperature: Kelvin) -> Self {
+        SUN_RADIUS * (luminosity / SUN_LUMINOSITY).sqrt()
+            / (temperature / SOLAR_TEMPERATURE).powi(2)
+    }
+}
+
 impl Unit for SolarRadius {
     const SYMBOL: &'static str = "R☉";
     const NAME: &'static str = "solar radius";
 }
 
-#[derive(Copy, Clone, Display, PartialEq, PartialOrd)]
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct SolarLuminosity(f32);
 
 impl SolarLuminosity {
-    pub fn new(luminosity: f32) -> Self {
+    pub const fn new(luminosity: f32) -> Self {
         SolarLuminosity(luminosity)
     }
 
-    /// TODO (Wybe 2022-07-03): Make dependent on age as well?
+    /// Luminosity of a star of this mass at the same point in its life as the sun is now.
+    /// Use [SolarLuminosity::calculate_at_age] for the luminosity at other ages.
+    ///
     /// TODO (Wybe 2022-07-03): Make the magic numbers no longer magic.
     pub fn calculate(mass: SolarMass) -> Self {
-        let mass = mass.0;
+        let mass = mass / SUN_MASS;
 
         let luminosity = if mass < 0.43 {
             mass.powf(2.3) * 0.23
@@ -66,7 +129,17 @@ impl SolarLuminosity {
             mass.powf(3.5) * 1.4
         };
 
-        SolarLuminosity(luminosity)
+        SUN_LUMINOSITY * luminosity
+    }
+
+    /// Luminosity at the given fraction of the main sequence lifetime.
+    /// Based on the relation from [Gough 1981](https://doi.org/10.1007/BF00151235)
+    /// for the brightening of the sun, scaled to the lifetime of the star.
+    pub fn calculate_at_age(mass: SolarMass, lifetime_fraction: f32) -> Self {
+        let fraction = lifetime_fraction.clamp(0., 1.);
+        let reference = Self::calculate(mass);
+
+        reference / (1. + LUMINOSITY_EVOLUTION_FACTOR * (1. - fraction / SOLAR_LIFETIME_FRACTION))
     }
 }
 
@@ -75,13 +148,13 @@ impl Unit for SolarLuminosity {
     const NAME: &'static str = "solar luminosity";
 }
 
-#[derive(Copy, Clone, Display)]
+#[derive(Copy, Clone, Display, From, Into, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct SolarDensity(f32);
 
 impl SolarDensity {
-    /// TODO (Wybe 2022-07-03): Make the magic numbers no longer magic.
     pub fn calculate(mass: SolarMass, radius: SolarRadius) -> Self {
-        SolarDensity(mass.0 / radius.0.powi(3))
+        mass / radius.cubed()
     }
 }
 
@@ -90,7 +163,7 @@ impl Unit for SolarDensity {
     const NAME: &'static str = "solar density";
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SpectralClass {
     O,
     A,
@@ -99,6 +172,12 @@ pub enum SpectralClass {
     G,
     K,
     M,
+    /// Coolest stars, and the hottest brown dwarfs.
+    L,
+    /// Methane dwarfs.
+    T,
+    /// The coolest brown dwarfs, some of them are colder than a cup of coffee.
+    Y,
 }
 
 impl SpectralClass {
@@ -107,9 +186,10 @@ impl SpectralClass {
     /// [The Stellar Classification Wikipedia article](https://en.wikipedia.org/wiki/Stellar_classification)
     pub fn calculate(temperature: Kelvin) -> Self {
         match temperature.0 as u32 {
-            // TODO (Wybe 2022-07-03): What to to with objects lower than 2400K? These are not actually M class stars.
-            0..=2400 => SpectralClass::M,
-            2400..=3700 => SpectralClass::M,
+            0..=500 => SpectralClass::Y,
+            501..=1300 => SpectralClass::T,
+            1301..=2400 => SpectralClass::L,
+            2401..=3700 => SpectralClass::M,
             3701..=5200 => SpectralClass::K,
             5201..=6000 => SpectralClass::G,
             6001..=7500 => SpectralClass::F,
@@ -126,19 +206,593 @@ impl Display for SpectralClass {
     }
 }
 
-#[derive(Copy, Clone, Display, From, Into, Sub)]
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Kelvin(f32);
 
+impl Kelvin {
+    pub const fn new(temperature: f32) -> Self {
+        Kelvin(temperature)
+    }
+}
+
 impl Unit for Kelvin {
     const SYMBOL: &'static str = "K";
     const NAME: &'static str = "kelvin";
 }
 
-#[derive(Clone)]
+/// Water freezes at 0 °C and boils at 100 °C (at the surface of the earth).
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Celsius(f32);
+
+impl From<Kelvin> for Celsius {
+    fn from(temperature: Kelvin) -> Self {
+        Celsius(temperature.0 - ZERO_CELSIUS_K)
+    }
+}
+
+impl From<Celsius> for Kelvin {
+    fn from(temperature: Celsius) -> Self {
+        Kelvin(temperature.0 + ZERO_CELSIUS_K)
+    }
+}
+
+impl Unit for Celsius {
+    const SYMBOL: &'static str = "°C";
+    const NAME: &'static str = "degree celsius";
+}
+
+const ZERO_CELSIUS_K: f32 = 273.15;
+
+/// AU = 1.495978707 * 10^11 m, the average distance between the earth and the sun.
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AstronomicalUnit(f32);
+
+impl AstronomicalUnit {
+    pub const fn new(distance: f32) -> Self {
+        AstronomicalUnit(distance)
+    }
+}
+
+impl Unit for AstronomicalUnit {
+    const SYMBOL: &'static str = "AU";
+    const NAME: &'static str = "astronomical unit";
+}
+
+/// A year on earth, 365.256 days.
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EarthYear(f32);
+
+impl EarthYear {
+    pub fn squared(self) -> SquareEarthYear {
+        SquareEarthYear(self.0.powi(2))
+    }
+}
+
+impl Unit for EarthYear {
+    const SYMBOL: &'static str = "yr";
+    const NAME: &'static str = "earth year";
+}
+
+/// A day on earth, 24 hours.
+#[derive(
+    Copy, Clone, Default, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct EarthDay(f32);
+
+impl EarthDay {
+    pub const fn new(days: f32) -> Self {
+        EarthDay(days)
+    }
+}
+
+impl From<EarthYear> for EarthDay {
+    fn from(year: EarthYear) -> Self {
+        EarthDay(year.0 * DAYS_PER_EARTH_YEAR)
+    }
+}
+
+impl Unit for EarthDay {
+    const SYMBOL: &'static str = "d";
+    const NAME: &'static str = "earth day";
+}
+
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Hour(f32);
+
+impl Unit for Hour {
+    const SYMBOL: &'static str = "h";
+    const NAME: &'static str = "hour";
+}
+
+/// A billion years, for the ages and lifetimes of stars.
+#[derive(
+    Copy, Clone, Default, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Gigayear(f32);
+
+impl Gigayear {
+    pub const fn new(age: f32) -> Self {
+        Gigayear(age)
+    }
+}
+
+impl Unit for Gigayear {
+    const SYMBOL: &'static str = "Gyr";
+    const NAME: &'static str = "gigayear";
+}
+
+#[derive(
+    Copy, Clone, Default, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Degree(f32);
+
+impl Degree {
+    pub const fn new(angle: f32) -> Self {
+        Degree(angle)
+    }
+
+    pub fn from_radians(angle: f32) -> Self {
+        Degree(angle.to_degrees())
+    }
+
+    pub fn to_radians(self) -> f32 {
+        self.0.to_radians()
+    }
+}
+
+impl Unit for Degree {
+    const SYMBOL: &'static str = "°";
+    const NAME: &'static str = "degree";
+}
+
+/// Amount of energy received from a star, relative to what the earth receives from the sun.
+/// S⊕ = 1361 W/m²
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EarthInsolation(f32);
+
+impl Unit for EarthInsolation {
+    const SYMBOL: &'static str = "S⊕";
+    const NAME: &'static str = "earth insolation";
+}
+
+/// M⊕ = 5.9722 * 10^24 kg
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EarthMass(f32);
+
+impl EarthMass {
+    pub const fn new(mass: f32) -> Self {
+        EarthMass(mass)
+    }
+}
+
+impl From<SolarMass> for EarthMass {
+    fn from(mass: SolarMass) -> Self {
+        EarthMass(mass.0 * EARTH_MASSES_PER_SOLAR_MASS)
+    }
+}
+
+impl From<JupiterMass> for EarthMass {
+    fn from(mass: JupiterMass) -> Self {
+        EarthMass(mass.0 * EARTH_MASSES_PER_JUPITER_MASS)
+    }
+}
+
+impl Unit for EarthMass {
+    const SYMBOL: &'static str = "M⊕";
+    const NAME: &'static str = "earth mass";
+}
+
+/// R⊕ = 6371 km
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EarthRadius(f32);
+
+impl EarthRadius {
+    pub const fn new(radius: f32) -> Self {
+        EarthRadius(radius)
+    }
+}
+
+impl From<JupiterRadius> for EarthRadius {
+    fn from(radius: JupiterRadius) -> Self {
+        EarthRadius(radius.0 * EARTH_RADII_PER_JUPITER_RADIUS)
+    }
+}
+
+impl From<EarthRadius> for JupiterRadius {
+    fn from(radius: EarthRadius) -> Self {
+        JupiterRadius(radius.0 / EARTH_RADII_PER_JUPITER_RADIUS)
+    }
+}
+
+impl Unit for EarthRadius {
+    const SYMBOL: &'static str = "R⊕";
+    const NAME: &'static str = "earth radius";
+}
+
+/// ρ⊕ = 5.514 g/cm³
+#[derive(Copy, Clone, Display, From, Into, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EarthDensity(f32);
+
+impl EarthDensity {
+    pub fn calculate(mass: EarthMass, radius: EarthRadius) -> Self {
+        mass / radius.cubed()
+    }
+}
+
+impl Unit for EarthDensity {
+    const SYMBOL: &'static str = "ρ⊕";
+    const NAME: &'static str = "earth density";
+}
+
+/// Surface gravity relative to that of the earth.
+/// g = 9.80665 m/s²
+#[derive(Copy, Clone, Display, From, Into, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Gravity(f32);
+
+impl Gravity {
+    pub fn calculate(mass: EarthMass, radius: EarthRadius) -> Self {
+        mass / radius.squared()
+    }
+}
+
+impl Unit for Gravity {
+    const SYMBOL: &'static str = "g";
+    const NAME: &'static str = "standard gravity";
+}
+
+#[derive(
+    Copy, Clone, Default, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Kilometer(f32);
+
+impl Kilometer {
+    pub const fn new(distance: f32) -> Self {
+        Kilometer(distance)
+    }
+}
+
+impl From<EarthRadius> for Kilometer {
+    fn from(radius: EarthRadius) -> Self {
+        Kilometer(radius.0 * KILOMETERS_PER_EARTH_RADIUS)
+    }
+}
+
+impl From<AstronomicalUnit> for Kilometer {
+    fn from(distance: AstronomicalUnit) -> Self {
+        Kilometer(distance.0 * KILOMETERS_PER_ASTRONOMICAL_UNIT)
+    }
+}
+
+impl Unit for Kilometer {
+    const SYMBOL: &'static str = "km";
+    const NAME: &'static str = "kilometer";
+}
+
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct KilometerPerSecond(f32);
+
+impl Unit for KilometerPerSecond {
+    const SYMBOL: &'static str = "km/s";
+    const NAME: &'static str = "kilometer per second";
+}
+
+#[derive(Copy, Clone, Display, From, Into, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SquareKilometer(f32);
+
+impl Unit for SquareKilometer {
+    const SYMBOL: &'static str = "km²";
+    const NAME: &'static str = "square kilometer";
+}
+
+/// The atmosphere of the earth presses down with about 1.013 bar at sea level.
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Bar(f32);
+
+impl Unit for Bar {
+    const SYMBOL: &'static str = "bar";
+    const NAME: &'static str = "bar";
+}
+
+/// V☉, the volume of a sphere with a radius of one [SolarRadius].
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SolarVolume(f32);
+
+impl Unit for SolarVolume {
+    const SYMBOL: &'static str = "V☉";
+    const NAME: &'static str = "solar volume";
+}
+
+/// V⊕, the volume of a sphere with a radius of one [EarthRadius].
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EarthVolume(f32);
+
+impl Unit for EarthVolume {
+    const SYMBOL: &'static str = "V⊕";
+    const NAME: &'static str = "earth volume";
+}
+
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SquareEarthRadius(f32);
+
+impl Unit for SquareEarthRadius {
+    const SYMBOL: &'static str = "R⊕²";
+    const NAME: &'static str = "square earth radius";
+}
+
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SquareAstronomicalUnit(f32);
+
+impl SquareAstronomicalUnit {
+    pub fn sqrt(self) -> AstronomicalUnit {
+        AstronomicalUnit(self.0.sqrt())
+    }
+}
+
+impl Unit for SquareAstronomicalUnit {
+    const SYMBOL: &'static str = "AU²";
+    const NAME: &'static str = "square astronomical unit";
+}
+
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CubicAstronomicalUnit(f32);
+
+impl Unit for CubicAstronomicalUnit {
+    const SYMBOL: &'static str = "AU³";
+    const NAME: &'static str = "cubic astronomical unit";
+}
+
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SquareSolarRadius(f32);
+
+impl Unit for SquareSolarRadius {
+    const SYMBOL: &'static str = "R☉²";
+    const NAME: &'static str = "square solar radius";
+}
+
+/// Energy radiated by each part of the surface of a star, relative to the surface of the sun.
+/// Only depends on the temperature of the surface.
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SolarSurfaceFlux(f32);
+
+impl Unit for SolarSurfaceFlux {
+    const SYMBOL: &'static str = "F☉";
+    const NAME: &'static str = "solar surface flux";
+}
+
+/// Kepler's third law gives the square of the orbital period.
+#[derive(Copy, Clone, Display, From, Into, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SquareEarthYear(f32);
+
+impl SquareEarthYear {
+    pub fn sqrt(self) -> EarthYear {
+        EarthYear(self.0.sqrt())
+    }
+}
+
+impl Unit for SquareEarthYear {
+    const SYMBOL: &'static str = "yr²";
+    const NAME: &'static str = "square earth year";
+}
+
+impl SolarRadius {
+    pub fn squared(self) -> SquareSolarRadius {
+        SquareSolarRadius(self.0.powi(2))
+    }
+
+    pub fn cubed(self) -> SolarVolume {
+        SolarVolume(self.0.powi(3))
+    }
+}
+
+impl EarthRadius {
+    pub fn squared(self) -> SquareEarthRadius {
+        SquareEarthRadius(self.0.powi(2))
+    }
+
+    pub fn cubed(self) -> EarthVolume {
+        EarthVolume(self.0.powi(3))
+    }
+}
+
+impl AstronomicalUnit {
+    pub fn squared(self) -> SquareAstronomicalUnit {
+        SquareAstronomicalUnit(self.0.powi(2))
+    }
+
+    pub fn cubed(self) -> CubicAstronomicalUnit {
+        CubicAstronomicalUnit(self.0.powi(3))
+    }
+}
+
+/// Values of the same unit can be added to and subtracted from each other, and scaled by a
+/// number. Dividing two of them gives their ratio, which has no unit.
+/// Like for floats, `min` and `max` ignore NaN.
+macro_rules! impl_same_unit_arithmetic {
+    ($($unit:ident),* $(,)?) => {
+        $(
+            impl Add for $unit {
+                type Output = $unit;
+
+                fn add(self, rhs: $unit) -> $unit {
+                    $unit(self.0 + rhs.0)
+                }
+            }
+
+            impl Sub for $unit {
+                type Output = $unit;
+
+                fn sub(self, rhs: $unit) -> $unit {
+                    $unit(self.0 - rhs.0)
+                }
+            }
+
+            impl Mul<f32> for $unit {
+                type Output = $unit;
+
+                fn mul(self, rhs: f32) -> $unit {
+                    $unit(self.0 * rhs)
+                }
+            }
+
+            impl Div<f32> for $unit {
+                type Output = $unit;
+
+                fn div(self, rhs: f32) -> $unit {
+                    $unit(self.0 / rhs)
+                }
+            }
+
+            impl Div for $unit {
+                type Output = f32;
+
+                fn div(self, rhs: $unit) -> f32 {
+                    self.0 / rhs.0
+                }
+            }
+
+            impl $unit {
+                pub fn min(self, other: $unit) -> $unit {
+                    $unit(self.0.min(other.0))
+                }
+
+                pub fn max(self, other: $unit) -> $unit {
+                    $unit(self.0.max(other.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_same_unit_arithmetic!(
+    SolarMass,
+    JupiterMass,
+    JupiterRadius,
+    SolarRadius,
+    SquareSolarRadius,
+    SolarLuminosity,
+    SolarSurfaceFlux,
+    SolarDensity,
+    SolarVolume,
+    Kelvin,
+    AstronomicalUnit,
+    SquareAstronomicalUnit,
+    CubicAstronomicalUnit,
+    EarthYear,
+    SquareEarthYear,
+    EarthDay,
+    Hour,
+    Gigayear,
+    Degree,
+    EarthInsolation,
+    EarthMass,
+    EarthRadius,
+    SquareEarthRadius,
+    EarthVolume,
+    EarthDensity,
+    Gravity,
+    Kilometer,
+    KilometerPerSecond,
+    SquareKilometer,
+    Bar,
+);
+
+/// `lhs / rhs = output`, and the products and quotients that follow from it.
+/// Only for units that are defined relative to the same body, so that no conversion
+/// factor is needed: one solar mass in one solar volume is one solar density.
+///
+/// Units that don't belong together don't compile:
+///
+/// ```compile_fail
+/// use worldsmith_lib::units::*;
+///
+/// let density = SolarMass::new(1.) / AstronomicalUnit::new(1.).squared();
+/// ```
+macro_rules! impl_unit_quotient {
+    ($lhs:ident / $rhs:ident = $output:ident) => {
+        impl Div<$rhs> for $lhs {
+            type Output = $output;
+
+            fn div(self, rhs: $rhs) -> $output {
+                $output(self.0 / rhs.0)
+            }
+        }
+
+        impl Div<$output> for $lhs {
+            type Output = $rhs;
+
+            fn div(self, rhs: $output) -> $rhs {
+                $rhs(self.0 / rhs.0)
+            }
+        }
+
+        impl Mul<$rhs> for $output {
+            type Output = $lhs;
+
+            fn mul(self, rhs: $rhs) -> $lhs {
+                $lhs(self.0 * rhs.0)
+            }
+        }
+
+        impl Mul<$output> for $rhs {
+            type Output = $lhs;
+
+            fn mul(self, rhs: $output) -> $lhs {
+                $lhs(self.0 * rhs.0)
+            }
+        }
+    };
+}
+
+impl_unit_quotient!(SolarMass / SolarVolume = SolarDensity);
+impl_unit_quotient!(EarthMass / EarthVolume = EarthDensity);
+impl_unit_quotient!(EarthMass / SquareEarthRadius = Gravity);
+impl_unit_quotient!(SolarLuminosity / SquareAstronomicalUnit = EarthInsolation);
+impl_unit_quotient!(SolarLuminosity / SquareSolarRadius = SolarSurfaceFlux);
+impl_unit_quotient!(CubicAstronomicalUnit / SolarMass = SquareEarthYear);
+
+// The sun and the earth, which the solar and earth units are relative to.
+// Dividing by these gives how many times heavier, brighter or further away something is,
+// for relations that are fitted to the sun or the earth.
+pub(crate) const SUN_MASS: SolarMass = SolarMass(1.);
+pub(crate) const SUN_RADIUS: SolarRadius = SolarRadius(1.);
+pub(crate) const SUN_LUMINOSITY: SolarLuminosity = SolarLuminosity(1.);
+const SUN_SURFACE_FLUX: SolarSurfaceFlux = SolarSurfaceFlux(1.);
+pub(crate) const EARTH_MASS: EarthMass = EarthMass(1.);
+pub(crate) const EARTH_RADIUS: EarthRadius = EarthRadius(1.);
+pub(crate) const EARTH_ORBIT: AstronomicalUnit = AstronomicalUnit(1.);
+pub(crate) const GIGAYEAR: Gigayear = Gigayear(1.);
+
+/// An sRGB color.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct ColorRgb(u8, u8, u8);
 
 impl ColorRgb {
-    pub fn new(r: u8, g: u8, b: u8) -> Self {
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
         ColorRgb(r, g, b)
     }
 
@@ -155,14 +809,155 @@ impl ColorRgb {
     }
 }
 
-/// TODO (Wybe 2022-07-03): Make the magic numbers no longer magic.
-pub fn calculate_maximum_age_gigayears(mass: SolarMass, luminosity: SolarLuminosity) -> f32 {
-    (mass.0 / luminosity.0) * 10.
+/// How long the sun stays on the main sequence.
+const SOLAR_MAIN_SEQUENCE_LIFETIME: Gigayear = Gigayear(10.);
+
+/// Age at which a star leaves the main sequence. Heavier stars are much brighter,
+/// so they burn through their hydrogen faster.
+pub fn calculate_maximum_age(mass: SolarMass, luminosity: SolarLuminosity) -> Gigayear {
+    SOLAR_MAIN_SEQUENCE_LIFETIME * ((mass / SUN_MASS) / (luminosity / SUN_LUMINOSITY))
 }
 
-/// TODO (Wybe 2022-07-03): Make the magic numbers no longer magic.
+/// Surface temperature of the sun.
+const SOLAR_TEMPERATURE: Kelvin = Kelvin(5776.);
+/// Fraction of its main sequence lifetime that the sun has lived.
+/// The mass-luminosity and mass-radius relations are fitted to present-day stars,
+/// so they are taken to hold at this point in the life of every star.
+pub const SOLAR_LIFETIME_FRACTION: f32 = 0.46;
+/// How much dimmer the sun was when it formed: 1 / (1 + 0.4) ≈ 70% of its current luminosity.
+const LUMINOSITY_EVOLUTION_FACTOR: f32 = 0.4;
+/// How much cooler the sun was when it formed: about 2%.
+const TEMPERATURE_EVOLUTION_FACTOR: f32 = 0.02;
+
 pub fn calculate_stellar_temperature(radius: SolarRadius, luminosity: SolarLuminosity) -> Kelvin {
-    Kelvin((luminosity.0 / radius.0.powi(2)).powf(0.25) * 5776.)
+    SOLAR_TEMPERATURE * (luminosity / radius.squared() / SUN_SURFACE_FLUX).powf(0.25)
+}
+
+/// Luminosity of a black body with the given radius and surface temperature.
+pub fn calculate_black_body_luminosity(
+    radius: SolarRadius,
+    temperature: Kelvin,
+) -> SolarLuminosity {
+    SUN_SURFACE_FLUX * (temperature / SOLAR_TEMPERATURE).powi(4) * radius.squared()
+}
+
+/// Main sequence stars heat up slightly as they age. This is fitted to the sun,
+/// which goes from about 5660 K when it formed to about 5900 K at the end of the main sequence.
+pub fn calculate_stellar_temperature_at_age(mass: SolarMass, lifetime_fraction: f32) -> Kelvin {
+    let fraction = lifetime_fraction.clamp(0., 1.);
+    let reference = calculate_stellar_temperature(
+        SolarRadius::calculate(mass),
+        SolarLuminosity::calculate(mass),
+    );
+
+    reference * (1. + TEMPERATURE_EVOLUTION_FACTOR * (fraction / SOLAR_LIFETIME_FRACTION - 1.))
+}
+
+/// Stellar flux at which a planet is too hot to keep liquid water.
+const HABITABLE_ZONE_INNER_EDGE_FLUX: EarthInsolation = EarthInsolation(1.1);
+/// Stellar flux at which a planet is too cold to keep liquid water.
+const HABITABLE_ZONE_OUTER_EDGE_FLUX: EarthInsolation = EarthInsolation(0.53);
+/// Distance of the frost line for a star with 1 solar luminosity.
+const SOLAR_FROST_LINE: AstronomicalUnit = AstronomicalUnit(4.85);
+/// Distance of the inner limit of the planetary system for a star with 1 solar mass.
+const SOLAR_INNER_SYSTEM_LIMIT: AstronomicalUnit = AstronomicalUnit(0.1);
+/// Distance of the outer limit of the planetary system for a star with 1 solar mass.
+const SOLAR_OUTER_SYSTEM_LIMIT: AstronomicalUnit = AstronomicalUnit(40.);
+
+/// Closest distance to the star at which a planet could still have liquid water on its surface.
+pub fn calculate_habitable_zone_inner_edge(luminosity: SolarLuminosity) -> AstronomicalUnit {
+    (luminosity / HABITABLE_ZONE_INNER_EDGE_FLUX).sqrt()
+}
+
+/// Furthest distance from the star at which a planet could still have liquid water on its surface.
+pub fn calculate_habitable_zone_outer_edge(luminosity: SolarLuminosity) -> AstronomicalUnit {
+    (luminosity / HABITABLE_ZONE_OUTER_EDGE_FLUX).sqrt()
+}
+
+/// Distance beyond which volatiles like water freeze into ice.
+/// Gas giants tend to form just outside of this line.
+pub fn calculate_frost_line(luminosity: SolarLuminosity) -> AstronomicalUnit {
+    SOLAR_FROST_LINE * (luminosity / SUN_LUMINOSITY).sqrt()
+}
+
+/// Closest distance to the star at which planets can form.
+pub fn calculate_inner_system_limit(mass: SolarMass) -> AstronomicalUnit {
+    SOLAR_INNER_SYSTEM_LIMIT * (mass / SUN_MASS)
+}
+
+/// Furthest distance from the star at which planets can form.
+pub fn calculate_outer_system_limit(mass: SolarMass) -> AstronomicalUnit {
+    SOLAR_OUTER_SYSTEM_LIMIT * (mass / SUN_MASS)
+}
+
+pub(crate) const SOLAR_MASSES_PER_JUPITER_MASS: f32 = 0.0009546;
+pub(crate) const EARTH_MASSES_PER_JUPITER_MASS: f32 = 317.83;
+const EARTH_RADII_PER_JUPITER_RADIUS: f32 = 10.973;
+const EARTH_MASSES_PER_SOLAR_MASS: f32 = 332_946.;
+const KILOMETERS_PER_EARTH_RADIUS: f32 = 6371.;
+const KILOMETERS_PER_ASTRONOMICAL_UNIT: f32 = 149_597_870.7;
+const DAYS_PER_EARTH_YEAR: f32 = 365.256;
+/// Average velocity of the earth around the sun.
+const EARTH_ORBITAL_VELOCITY: KilometerPerSecond = KilometerPerSecond(29.78);
+
+/// Kepler's third law, with the mass of the orbiting body neglected.
+pub fn calculate_orbital_period(
+    semi_major_axis: AstronomicalUnit,
+    star_mass: SolarMass,
+) -> EarthYear {
+    (semi_major_axis.cubed() / star_mass).sqrt()
+}
+
+/// Velocity at the given distance from the star, for an orbit with the given semi-major axis.
+/// Uses the vis-viva equation.
+pub fn calculate_orbital_velocity(
+    distance: AstronomicalUnit,
+    semi_major_axis: AstronomicalUnit,
+    star_mass: SolarMass,
+) -> KilometerPerSecond {
+    let distance = distance / EARTH_ORBIT;
+    let semi_major_axis = semi_major_axis / EARTH_ORBIT;
+
+    EARTH_ORBITAL_VELOCITY * (star_mass / SUN_MASS * (2. / distance - 1. / semi_major_axis)).sqrt()
+}
+
+/// Insolation averaged over a whole orbit.
+/// An eccentric orbit receives slightly more energy than a circular one with the same semi-major axis.
+pub fn calculate_mean_insolation(
+    luminosity: SolarLuminosity,
+    semi_major_axis: AstronomicalUnit,
+    eccentricity: f32,
+) -> EarthInsolation {
+    luminosity / (semi_major_axis.squared() * (1. - eccentricity.powi(2)).sqrt())
+}
+
+/// Equilibrium temperature of a body at the given distance from a star, heated only by the
+/// light of that star. Albedo is the fraction of that light that is reflected (0 to 1).
+/// This does not include any greenhouse effect, so the earth would only be 255 K.
+pub fn calculate_equilibrium_temperature(
+    luminosity: SolarLuminosity,
+    distance: AstronomicalUnit,
+    albedo: f32,
+) -> Kelvin {
+    EARTH_BLACK_BODY_TEMPERATURE * (luminosity * (1. - albedo) / SUN_LUMINOSITY).powf(0.25)
+        / (distance / EARTH_ORBIT).sqrt()
+}
+
+/// Equilibrium temperature of a black body (albedo of 0) at the distance of the earth.
+const EARTH_BLACK_BODY_TEMPERATURE: Kelvin = Kelvin(278.6);
+
+/// Escape velocity at the surface of the earth.
+const EARTH_ESCAPE_VELOCITY: KilometerPerSecond = KilometerPerSecond(11.186);
+/// Surface area of the earth.
+const EARTH_SURFACE_AREA: SquareKilometer = SquareKilometer(510_064_472.);
+
+/// Velocity needed to escape the gravity of a planet, starting from its surface.
+pub fn calculate_escape_velocity(mass: EarthMass, radius: EarthRadius) -> KilometerPerSecond {
+    EARTH_ESCAPE_VELOCITY * ((mass / EARTH_MASS) / (radius / EARTH_RADIUS)).sqrt()
+}
+
+pub fn calculate_surface_area(radius: EarthRadius) -> SquareKilometer {
+    EARTH_SURFACE_AREA * (radius.squared() / EARTH_RADIUS.squared())
 }
 
 #[cfg(test)]
@@ -184,4 +979,57 @@ mod tests {
         assert_eq!(15.839191, SolarLuminosity::calculate(SolarMass(2.)).0);
         assert_eq!(0.0625, SolarLuminosity::calculate(SolarMass(0.5)).0);
     }
+
+    #[test]
+    fn test_calculate_habitable_zone() {
+        let luminosity = SolarLuminosity::new(1.);
+        assert_eq!(0.9534626, calculate_habitable_zone_inner_edge(luminosity).0);
+        assert_eq!(1.3736057, calculate_habitable_zone_outer_edge(luminosity).0);
+        assert_eq!(4.85, calculate_frost_line(luminosity).0);
+    }
+
+    #[test]
+    fn test_calculate_system_limits() {
+        assert_eq!(0.1, calculate_inner_system_limit(SolarMass(1.)).0);
+        assert_eq!(40., calculate_outer_system_limit(SolarMass(1.)).0);
+        assert_eq!(20., calculate_outer_system_limit(SolarMass(0.5)).0);
+    }
+
+    #[test]
+    fn test_calculate_orbital_period() {
+        assert_eq!(1., calculate_orbital_period(1.0.into(), SolarMass(1.)).0);
+        assert_eq!(8., calculate_orbital_period(4.0.into(), SolarMass(1.)).0);
+        assert_eq!(365.256, EarthDay::from(EarthYear(1.)).0);
+    }
+
+    #[test]
+    fn test_calculate_solar_luminosity_at_age() {
+        assert_eq!(
+            1.,
+            SolarLuminosity::calculate_at_age(SolarMass(1.), SOLAR_LIFETIME_FRACTION).0
+        );
+        assert_eq!(
+            0.71428573,
+            SolarLuminosity::calculate_at_age(SolarMass(1.), 0.).0
+        );
+        assert_eq!(
+            1.8852459,
+            SolarLuminosity::calculate_at_age(SolarMass(1.), 1.).0
+        );
+    }
+
+    #[test]
+    fn test_unit_arithmetic() {
+        assert_eq!(0.25, (SolarMass(2.) / SolarRadius(2.).cubed()).0);
+        assert_eq!(2., (SolarDensity(0.25) * SolarRadius(2.).cubed()).0);
+        assert_eq!(
+            0.25,
+            (SolarLuminosity(1.) / AstronomicalUnit(2.).squared()).0
+        );
+        assert_eq!(2., (SolarLuminosity(1.) / EarthInsolation(0.25)).sqrt().0);
+        assert_eq!(1.5, (EarthMass(1.) + EarthMass(0.5)).0);
+        assert_eq!(2., Kelvin(300.) / Kelvin(150.));
+        assert_eq!(4., (SolarLuminosity(16.) / SolarRadius(2.).squared()).0);
+        assert_eq!(8., (AstronomicalUnit(4.).cubed() / SolarMass(1.)).sqrt().0);
+    }
 }